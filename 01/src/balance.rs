@@ -0,0 +1,210 @@
+use crate::Calories;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Above this many items, the exact solver is too slow and we fall back to LPT
+const EXACT_LIMIT: usize = 24;
+// Nodes explored by the exact solver in `balance` before keeping the best partition found so far
+const NODE_BUDGET: usize = 200_000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Partition {
+    elves: Vec<Vec<Calories>>,
+}
+
+impl Partition {
+    fn new(elves: Vec<Vec<Calories>>) -> Self {
+        Partition { elves }
+    }
+
+    pub fn elves(&self) -> &[Vec<Calories>] {
+        &self.elves
+    }
+
+    pub fn loads(&self) -> Vec<Calories> {
+        self.elves.iter().map(|items| items.iter().sum()).collect()
+    }
+
+    pub fn max_load(&self) -> Calories {
+        self.loads().into_iter().max().unwrap_or(0)
+    }
+}
+
+/// Distributes the items among `elves` elves, minimizing the maximum load.
+///
+/// Small inputs are searched exactly, larger ones use the LPT heuristic. The search is
+/// bounded, so the result is not always optimal, but is never worse than LPT.
+pub fn balance(items: &[Calories], elves: usize) -> Option<Partition> {
+    if items.len() <= EXACT_LIMIT {
+        balance_bounded(items, elves, NODE_BUDGET)
+    } else {
+        balance_greedy(items, elves)
+    }
+}
+
+/// Longest processing time first: give the largest remaining item to the least loaded elf.
+pub fn balance_greedy(items: &[Calories], elves: usize) -> Option<Partition> {
+    if elves == 0 {
+        return None;
+    }
+
+    let mut sorted = items.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+
+    let mut partition = vec![Vec::new(); elves];
+    let mut heap = (0..elves)
+        .map(|elf| Reverse((0, elf)))
+        .collect::<BinaryHeap<_>>();
+
+    for item in sorted {
+        let Reverse((load, elf)) = heap.pop()?;
+        partition[elf].push(item);
+        heap.push(Reverse((load + item, elf)));
+    }
+
+    Some(Partition::new(partition))
+}
+
+/// Branch and bound search for an optimal partition. Exponential, only use it on small inputs.
+pub fn balance_exact(items: &[Calories], elves: usize) -> Option<Partition> {
+    balance_bounded(items, elves, usize::MAX)
+}
+
+fn balance_bounded(items: &[Calories], elves: usize, budget: usize) -> Option<Partition> {
+    let greedy = balance_greedy(items, elves)?;
+
+    let mut sorted = items.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+
+    let total = sorted.iter().sum::<Calories>();
+    let largest = sorted.first().copied().unwrap_or(0);
+    let lower_bound = largest.max(total.div_ceil(elves as Calories));
+
+    let mut search = Search {
+        items: sorted,
+        loads: vec![0; elves],
+        assignment: Vec::new(),
+        best: greedy.max_load(),
+        best_assignment: None,
+        lower_bound,
+        budget,
+    };
+    search.run(0);
+
+    match search.best_assignment {
+        Some(assignment) => {
+            let mut partition = vec![Vec::new(); elves];
+            for (item, elf) in search.items.iter().zip(assignment) {
+                partition[elf].push(*item);
+            }
+            Some(Partition::new(partition))
+        }
+        None => Some(greedy),
+    }
+}
+
+struct Search {
+    items: Vec<Calories>,
+    loads: Vec<Calories>,
+    assignment: Vec<usize>,
+    best: Calories,
+    best_assignment: Option<Vec<usize>>,
+    lower_bound: Calories,
+    budget: usize,
+}
+
+impl Search {
+    fn run(&mut self, index: usize) {
+        // Cannot do better than the lower bound
+        if self.best == self.lower_bound || self.budget == 0 {
+            return;
+        }
+        self.budget -= 1;
+
+        if index == self.items.len() {
+            self.best = self.loads.iter().copied().max().unwrap_or(0);
+            self.best_assignment = Some(self.assignment.clone());
+            return;
+        }
+
+        // Items are sorted, so elves which cannot take the smallest remaining item
+        // below the best load cannot take any other one
+        let remaining = self.items[index..].iter().sum::<Calories>();
+        let smallest = self.items[self.items.len() - 1];
+        let room = self
+            .loads
+            .iter()
+            // An ancestor may still hold an elf at the best load found since
+            .filter_map(|load| (self.best - 1).checked_sub(*load))
+            .filter(|room| *room >= smallest)
+            .sum::<Calories>();
+        if room < remaining {
+            return;
+        }
+
+        let item = self.items[index];
+        for elf in 0..self.loads.len() {
+            // Elves with the same load are interchangeable
+            if self.loads[..elf].contains(&self.loads[elf]) {
+                continue;
+            }
+
+            // Only look for strictly better partitions than the best one
+            let load = self.loads[elf] + item;
+            if load >= self.best {
+                continue;
+            }
+
+            self.loads[elf] = load;
+            self.assignment.push(elf);
+            self.run(index + 1);
+            self.assignment.pop();
+            self.loads[elf] -= item;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_balance_greedy() {
+        let partition = balance_greedy(&[8, 7, 6, 5, 4], 2).unwrap();
+        assert_eq!(partition.max_load(), 17);
+        assert_eq!(partition.loads().iter().sum::<Calories>(), 30);
+    }
+
+    #[test]
+    fn test_balance_exact() {
+        let partition = balance_exact(&[8, 7, 6, 5, 4], 2).unwrap();
+        assert_eq!(partition.max_load(), 15);
+        assert_eq!(partition.loads().iter().sum::<Calories>(), 30);
+
+        let partition = balance(&[1000, 2000, 3000, 4000, 5000, 6000], 3).unwrap();
+        assert_eq!(partition.max_load(), 7000);
+
+        let partition = balance_exact(&[11, 8, 15, 15, 19, 14, 15], 3).unwrap();
+        assert_eq!(partition.max_load(), 34);
+    }
+
+    #[test]
+    fn test_balance_limit() {
+        let items = (0..EXACT_LIMIT as Calories)
+            .map(|i| 1000 + i * 37 % 101)
+            .collect::<Vec<_>>();
+        let start = Instant::now();
+        let partition = balance(&items, 5).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(2));
+        let greedy = balance_greedy(&items, 5).unwrap();
+        assert!(partition.max_load() <= greedy.max_load());
+        assert_eq!(partition.elves().iter().map(Vec::len).sum::<usize>(), 24);
+    }
+
+    #[test]
+    fn test_balance_no_elf() {
+        assert!(balance(&[1, 2, 3], 0).is_none());
+        assert_eq!(balance(&[], 2).unwrap().max_load(), 0);
+    }
+}
@@ -3,6 +3,12 @@ use std::num::ParseIntError;
 use std::str::FromStr;
 use thiserror::Error;
 
+mod balance;
+mod tracker;
+
+pub use balance::{balance, balance_exact, balance_greedy, Partition};
+pub use tracker::{CalorieTracker, ElfId, TrackerSnapshot};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("I/O error")]
@@ -14,28 +20,48 @@ pub enum Error {
 type Calories = u32;
 
 pub fn read_input<R>(read: R) -> Result<Vec<Calories>, Error>
+where
+    R: Read,
+{
+    let inventory = read_inventory(read)?;
+    Ok(inventory
+        .into_iter()
+        .map(|items| items.into_iter().sum())
+        .collect())
+}
+
+pub fn read_inventory<R>(read: R) -> Result<Vec<Vec<Calories>>, Error>
 where
     R: Read,
 {
     let mut elves = Vec::new();
-    let mut total_calories = 0;
+    let mut items = Vec::new();
 
     let lines = BufReader::new(read).lines();
     for line in lines {
         let line = line?;
-        if line.is_empty() {
-            elves.push(total_calories);
-            total_calories = 0;
-        } else {
-            let calories = u32::from_str(&line)?;
-            total_calories += calories;
+        match parse_line(&line)? {
+            Some(calories) => items.push(calories),
+            None => {
+                elves.push(items);
+                items = Vec::new();
+            }
         }
     }
 
-    elves.push(total_calories);
+    elves.push(items);
     Ok(elves)
 }
 
+// An empty line separates elves, any other line is a food quantity
+fn parse_line(line: &str) -> Result<Option<Calories>, Error> {
+    if line.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(u32::from_str(line)?))
+    }
+}
+
 pub fn find_max_calories(elves: &[Calories]) -> Option<Calories> {
     elves.iter().copied().max()
 }
@@ -46,12 +72,7 @@ pub fn find_calories_top(mut elves: Vec<Calories>, count: usize) -> Calories {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse() {
-        let test_str = r#"1000
+pub(crate) const TEST_STR: &str = r#"1000
 2000
 3000
 
@@ -66,7 +87,14 @@ mod tests {
 
 10000
 "#;
-        let actual = read_input(test_str.as_bytes()).unwrap();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let actual = read_input(TEST_STR.as_bytes()).unwrap();
         let expected = vec![6000, 4000, 11000, 24000, 10000];
         assert_eq!(actual, expected);
 
@@ -76,4 +104,17 @@ mod tests {
         let top_3 = find_calories_top(actual, 3);
         assert_eq!(top_3, 45000);
     }
+
+    #[test]
+    fn test_read_inventory() {
+        let test_str = r#"1000
+2000
+
+4000
+
+"#;
+        let actual = read_inventory(test_str.as_bytes()).unwrap();
+        let expected = vec![vec![1000, 2000], vec![4000], vec![]];
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,211 @@
+use crate::{parse_line, Calories, Error};
+use std::collections::BTreeSet;
+
+pub type ElfId = usize;
+
+/// Keeps track of the elves' calories while their inventories are being fed.
+///
+/// Only the best `top_count` elves are kept ordered, so adding calories is O(log k) and reading
+/// the top is O(k). Removing one of the best elves scans the others for a replacement.
+#[derive(Clone, Debug)]
+pub struct CalorieTracker {
+    top_count: usize,
+    elves: Vec<Option<Calories>>,
+    elf_count: usize,
+    current: Option<ElfId>,
+    // At least one elf is kept, to find the maximum
+    top: BTreeSet<(Calories, ElfId)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackerSnapshot {
+    pub elf_count: usize,
+    pub max: Option<Calories>,
+    pub top: Vec<Calories>,
+}
+
+impl CalorieTracker {
+    pub fn new(top_count: usize) -> Self {
+        let mut tracker = CalorieTracker {
+            top_count,
+            elves: Vec::new(),
+            elf_count: 0,
+            current: None,
+            top: BTreeSet::new(),
+        };
+        tracker.start_elf();
+        tracker
+    }
+
+    pub fn push_line(&mut self, line: &str) -> Result<(), Error> {
+        match parse_line(line)? {
+            Some(calories) => self.push_item(calories),
+            None => {
+                self.end_elf();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn push_item(&mut self, calories: Calories) {
+        let elf = match self.current {
+            Some(elf) => elf,
+            None => self.start_elf(),
+        };
+
+        if let Some(total) = self.elves[elf] {
+            self.update(elf, Some(total), total + calories);
+        }
+    }
+
+    /// Closes the current elf, and starts a new one. Returns the closed elf, if any.
+    pub fn end_elf(&mut self) -> Option<ElfId> {
+        let closed = self.current.take();
+        self.start_elf();
+        closed
+    }
+
+    pub fn remove_elf(&mut self, elf: ElfId) -> Option<Calories> {
+        let total = self.elves.get_mut(elf)?.take()?;
+        self.elf_count -= 1;
+        if self.current == Some(elf) {
+            self.current = None;
+        }
+
+        // Any other elf can be the next best one
+        if self.top.remove(&(total, elf)) {
+            let next = self
+                .elves
+                .iter()
+                .enumerate()
+                .filter_map(|(elf, calories)| Some(((*calories)?, elf)))
+                .filter(|entry| !self.top.contains(entry))
+                .max();
+            self.top.extend(next);
+        }
+        Some(total)
+    }
+
+    pub fn current_elf(&self) -> Option<ElfId> {
+        self.current
+    }
+
+    pub fn calories(&self, elf: ElfId) -> Option<Calories> {
+        self.elves.get(elf).copied().flatten()
+    }
+
+    pub fn elf_count(&self) -> usize {
+        self.elf_count
+    }
+
+    pub fn max(&self) -> Option<Calories> {
+        self.top.last().map(|(calories, _)| *calories)
+    }
+
+    pub fn top(&self) -> Vec<Calories> {
+        self.top
+            .iter()
+            .rev()
+            .take(self.top_count)
+            .map(|(calories, _)| *calories)
+            .collect()
+    }
+
+    pub fn top_sum(&self) -> Calories {
+        self.top
+            .iter()
+            .rev()
+            .take(self.top_count)
+            .map(|(calories, _)| calories)
+            .sum()
+    }
+
+    /// Calories of the remaining elves, in arrival order
+    pub fn totals(&self) -> Vec<Calories> {
+        self.elves.iter().flatten().copied().collect()
+    }
+
+    pub fn snapshot(&self) -> TrackerSnapshot {
+        TrackerSnapshot {
+            elf_count: self.elf_count(),
+            max: self.max(),
+            top: self.top(),
+        }
+    }
+
+    fn start_elf(&mut self) -> ElfId {
+        let elf = self.elves.len();
+        self.elves.push(Some(0));
+        self.elf_count += 1;
+        self.current = Some(elf);
+        self.update(elf, None, 0);
+        elf
+    }
+
+    // Calories only grow, so an elf out of the top can only replace the lowest top elf
+    fn update(&mut self, elf: ElfId, old: Option<Calories>, new: Calories) {
+        self.elves[elf] = Some(new);
+        let in_top = old.is_some_and(|old| self.top.remove(&(old, elf)));
+        if in_top || self.top.len() < self.top_count.max(1) {
+            self.top.insert((new, elf));
+        } else if self.top.first().is_some_and(|lowest| *lowest < (new, elf)) {
+            self.top.pop_first();
+            self.top.insert((new, elf));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, TEST_STR};
+
+    #[test]
+    fn test_tracker() {
+        let mut tracker = CalorieTracker::new(3);
+        for line in TEST_STR.lines() {
+            tracker.push_line(line).unwrap();
+        }
+        assert_eq!(tracker.totals(), read_input(TEST_STR.as_bytes()).unwrap());
+        assert_eq!(tracker.max(), Some(24000));
+        assert_eq!(tracker.top_sum(), 45000);
+
+        assert_eq!(tracker.remove_elf(3), Some(24000));
+        assert_eq!(tracker.remove_elf(3), None);
+        let expected = TrackerSnapshot {
+            elf_count: 4,
+            max: Some(11000),
+            top: vec![11000, 10000, 6000],
+        };
+        assert_eq!(tracker.snapshot(), expected);
+    }
+
+    #[test]
+    fn test_tracker_incremental() {
+        let mut tracker = CalorieTracker::new(2);
+        tracker.push_item(100);
+        assert_eq!(tracker.max(), Some(100));
+        assert_eq!(tracker.end_elf(), Some(0));
+        tracker.push_item(50);
+        tracker.push_item(60);
+        assert_eq!(tracker.top(), vec![110, 100]);
+
+        tracker.remove_elf(1);
+        assert_eq!(tracker.current_elf(), None);
+        tracker.push_item(10);
+        assert_eq!(tracker.current_elf(), Some(2));
+        assert_eq!(tracker.top(), vec![100, 10]);
+
+        // Elves out of the top can come back when a top elf is removed
+        tracker.end_elf();
+        tracker.push_item(5);
+        tracker.remove_elf(0);
+        assert_eq!(tracker.top(), vec![10, 5]);
+        assert_eq!(tracker.elf_count(), 2);
+
+        let mut tracker = CalorieTracker::new(0);
+        tracker.push_item(100);
+        assert_eq!(tracker.max(), Some(100));
+        assert!(tracker.top().is_empty());
+    }
+}
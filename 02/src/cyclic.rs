@@ -0,0 +1,163 @@
+use crate::RoundResult::{self, Draw, Lose, Win};
+
+/// A game where an odd number of moves, at least 3, beat each other cyclically.
+///
+/// Moves are identified by their index. A move beats another one if the distance
+/// from the other move to it is odd, so each move beats and loses to half of the others.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CyclicGame {
+    moves: usize,
+}
+
+impl CyclicGame {
+    pub const ROCK_PAPER_SCISSORS: CyclicGame = CyclicGame { moves: 3 };
+    pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: CyclicGame = CyclicGame { moves: 5 };
+
+    pub const fn new(moves: usize) -> Option<Self> {
+        // With a single move, there is no way to win or lose
+        if moves >= 3 && moves % 2 == 1 {
+            Some(CyclicGame { moves })
+        } else {
+            None
+        }
+    }
+
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    pub fn outcome(&self, enemy: usize, yours: usize) -> RoundResult {
+        let distance = (yours + self.moves - enemy % self.moves) % self.moves;
+        if distance == 0 {
+            Draw
+        } else if distance % 2 == 1 {
+            Win
+        } else {
+            Lose
+        }
+    }
+
+    /// Closest move to the enemy's that reaches the goal
+    pub fn move_for_goal(&self, enemy: usize, goal: &RoundResult) -> usize {
+        let distance = match goal {
+            Win => 1,
+            Draw => 0,
+            Lose => self.moves - 1,
+        };
+        (enemy + distance) % self.moves
+    }
+}
+
+pub trait CyclicMove: Copy + Sized {
+    const GAME: CyclicGame;
+
+    fn index(&self) -> usize;
+
+    fn from_index(index: usize) -> Option<Self>;
+
+    fn against(&self, enemy: &Self) -> RoundResult {
+        Self::GAME.outcome(enemy.index(), self.index())
+    }
+
+    fn for_goal(enemy: &Self, goal: &RoundResult) -> Self {
+        let index = Self::GAME.move_for_goal(enemy.index(), goal);
+        Self::from_index(index).expect("A cyclic game move should exist for every index")
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RPSLS {
+    Rock,
+    Paper,
+    Scissors,
+    Spock,
+    Lizard,
+}
+
+impl RPSLS {
+    pub const ALL: [RPSLS; 5] = [
+        RPSLS::Rock,
+        RPSLS::Paper,
+        RPSLS::Scissors,
+        RPSLS::Spock,
+        RPSLS::Lizard,
+    ];
+}
+
+impl CyclicMove for RPSLS {
+    const GAME: CyclicGame = CyclicGame::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+
+    fn index(&self) -> usize {
+        match self {
+            RPSLS::Rock => 0,
+            RPSLS::Paper => 1,
+            RPSLS::Scissors => 2,
+            RPSLS::Spock => 3,
+            RPSLS::Lizard => 4,
+        }
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        RPSLS::ALL.get(index).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            CyclicGame::new(5),
+            Some(CyclicGame::ROCK_PAPER_SCISSORS_LIZARD_SPOCK)
+        );
+        assert_eq!(CyclicGame::new(4), None);
+        assert_eq!(CyclicGame::new(1), None);
+        assert_eq!(CyclicGame::new(0), None);
+    }
+
+    #[test]
+    fn test_rpsls() {
+        use RPSLS::*;
+
+        let wins = [
+            (Scissors, Paper),
+            (Paper, Rock),
+            (Rock, Lizard),
+            (Lizard, Spock),
+            (Spock, Scissors),
+            (Scissors, Lizard),
+            (Lizard, Paper),
+            (Paper, Spock),
+            (Spock, Rock),
+            (Rock, Scissors),
+        ];
+        for (winner, loser) in wins {
+            assert_eq!(winner.against(&loser), Win);
+            assert_eq!(loser.against(&winner), Lose);
+        }
+
+        for enemy in RPSLS::ALL {
+            assert_eq!(enemy.against(&enemy), Draw);
+            for goal in RoundResult::ALL {
+                assert_eq!(RPSLS::for_goal(&enemy, &goal).against(&enemy), goal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_balanced() {
+        let game = CyclicGame::new(7).unwrap();
+        for enemy in 0..7 {
+            let wins = (0..7)
+                .filter(|yours| game.outcome(enemy, *yours) == Win)
+                .count();
+            assert_eq!(wins, 3);
+            for goal in RoundResult::ALL {
+                assert_eq!(game.outcome(enemy, game.move_for_goal(enemy, &goal)), goal);
+            }
+        }
+    }
+}
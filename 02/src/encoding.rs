@@ -0,0 +1,208 @@
+use crate::RoundResult::{self, Draw, Lose, Win};
+use crate::RPS::{self, Paper, Rock, Scissors};
+use crate::{Error, Round};
+use std::str::FromStr;
+
+/// Which symbols of the strategy guide map to which moves and goals.
+///
+/// A spec is a list of `symbol=move` entries for the enemy's column, and
+/// `symbol=move/goal` entries for the second column, separated by whitespace or commas.
+/// The `ignore-case` entry makes ASCII symbols case-insensitive. Symbols must be unique in each column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Encoding {
+    enemy: Vec<(String, RPS)>,
+    response: Vec<(String, RPS, RoundResult)>,
+    ignore_case: bool,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::empty()
+            .with_enemy("A", Rock)
+            .with_enemy("B", Paper)
+            .with_enemy("C", Scissors)
+            .with_response("X", Rock, Lose)
+            .with_response("Y", Paper, Draw)
+            .with_response("Z", Scissors, Win)
+    }
+}
+
+impl Encoding {
+    /// An encoding without any symbol, to be filled with `with_enemy` and `with_response`
+    pub fn empty() -> Self {
+        Encoding {
+            enemy: Vec::new(),
+            response: Vec::new(),
+            ignore_case: false,
+        }
+    }
+
+    pub fn with_enemy(mut self, symbol: &str, enemy: RPS) -> Self {
+        self.enemy.push((symbol.to_string(), enemy));
+        self
+    }
+
+    pub fn with_response(mut self, symbol: &str, yours: RPS, goal: RoundResult) -> Self {
+        self.response.push((symbol.to_string(), yours, goal));
+        self
+    }
+
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn parse_line(&self, line: &str) -> Result<Round, Error> {
+        let (enemy, position) = self.parse_symbols(line)?;
+        let (_, yours, goal) = self.response[position];
+        Ok(Round::new(enemy, yours, goal))
+    }
+
+    /// Parses the enemy's move and the position of the second column symbol in the encoding
+    pub fn parse_symbols(&self, line: &str) -> Result<(RPS, usize), Error> {
+        let mut iter = line.split_whitespace();
+        if let (Some(enemy), Some(response), None) = (iter.next(), iter.next(), iter.next()) {
+            let enemy = self
+                .enemy
+                .iter()
+                .find(|(symbol, _)| self.matches(symbol, enemy))
+                .map(|(_, enemy)| *enemy)
+                .ok_or_else(|| Error::UnknownSymbol(enemy.to_string(), line.to_string()))?;
+            let position = self
+                .response
+                .iter()
+                .position(|(symbol, _, _)| self.matches(symbol, response))
+                .ok_or_else(|| Error::UnknownSymbol(response.to_string(), line.to_string()))?;
+            Ok((enemy, position))
+        } else {
+            Err(Error::Parse(line.to_string()))
+        }
+    }
+
+    fn matches(&self, symbol: &str, input: &str) -> bool {
+        if self.ignore_case {
+            symbol.eq_ignore_ascii_case(input)
+        } else {
+            symbol == input
+        }
+    }
+
+    fn find_duplicate(&self) -> Option<&str> {
+        let enemy = self.enemy.iter().map(|(symbol, _)| symbol.as_str());
+        let response = self.response.iter().map(|(symbol, _, _)| symbol.as_str());
+        for symbols in [enemy.collect::<Vec<_>>(), response.collect()] {
+            for (i, symbol) in symbols.iter().enumerate() {
+                if symbols[..i].iter().any(|other| self.matches(other, symbol)) {
+                    return Some(symbol);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|entry| !entry.is_empty());
+
+        let mut encoding = Encoding::empty();
+        for entry in entries {
+            if entry == "ignore-case" {
+                encoding.ignore_case = true;
+                continue;
+            }
+
+            let (symbol, meaning) = entry
+                .split_once('=')
+                .filter(|(symbol, _)| !symbol.is_empty())
+                .ok_or_else(|| Error::Encoding(entry.to_string()))?;
+            match meaning.split_once('/') {
+                Some((yours, goal)) => {
+                    let yours =
+                        parse_move(yours).ok_or_else(|| Error::Encoding(entry.to_string()))?;
+                    let goal =
+                        parse_goal(goal).ok_or_else(|| Error::Encoding(entry.to_string()))?;
+                    encoding.response.push((symbol.to_string(), yours, goal));
+                }
+                None => {
+                    let enemy =
+                        parse_move(meaning).ok_or_else(|| Error::Encoding(entry.to_string()))?;
+                    encoding.enemy.push((symbol.to_string(), enemy));
+                }
+            }
+        }
+
+        // Checked at the end, as `ignore-case` can come after the symbols
+        match encoding.find_duplicate() {
+            Some(symbol) => Err(Error::Encoding(symbol.to_string())),
+            None => Ok(encoding),
+        }
+    }
+}
+
+fn parse_move(input: &str) -> Option<RPS> {
+    match input.to_lowercase().as_str() {
+        "rock" => Some(Rock),
+        "paper" => Some(Paper),
+        "scissors" => Some(Scissors),
+        _ => None,
+    }
+}
+
+fn parse_goal(input: &str) -> Option<RoundResult> {
+    match input.to_lowercase().as_str() {
+        "win" => Some(Win),
+        "draw" => Some(Draw),
+        "lose" => Some(Lose),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec() {
+        let spec = "A=rock, B=paper, C=scissors\nX=rock/lose Y=paper/draw Z=scissors/win";
+        let actual = Encoding::from_str(spec).unwrap();
+        assert_eq!(actual, Encoding::default());
+
+        assert!(matches!(
+            Encoding::from_str("A=stone"),
+            Err(Error::Encoding(entry)) if entry == "A=stone"
+        ));
+        assert!(matches!(
+            Encoding::from_str("A=rock A=paper"),
+            Err(Error::Encoding(symbol)) if symbol == "A"
+        ));
+        assert!(matches!(
+            Encoding::from_str("x=rock/win X=paper/lose ignore-case"),
+            Err(Error::Encoding(symbol)) if symbol == "X"
+        ));
+        assert!(Encoding::from_str("x=rock/win X=paper/lose A=rock").is_ok());
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let encoding =
+            Encoding::from_str("r=rock p=paper 1=paper/lose 2=rock/win ignore-case").unwrap();
+        let actual = encoding.parse_line("  R \t 2 ").unwrap();
+        assert_eq!(actual, Round::new(Rock, Rock, Win));
+
+        assert!(matches!(
+            encoding.parse_line("s 1"),
+            Err(Error::UnknownSymbol(symbol, _)) if symbol == "s"
+        ));
+        assert!(matches!(encoding.parse_line("r 1 2"), Err(Error::Parse(_))));
+
+        assert_eq!(encoding.parse_symbols("p 2").unwrap(), (Paper, 1));
+
+        let strict = encoding.with_ignore_case(false);
+        assert!(strict.parse_line("R 2").is_err());
+    }
+}
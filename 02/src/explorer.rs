@@ -0,0 +1,170 @@
+use crate::{CyclicMove, Encoding, Error, RoundResult, ScoringRules, RPS};
+use std::io::{BufRead, BufReader, Read};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Meaning {
+    Move(RPS),
+    Goal(RoundResult),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MappingKind {
+    Moves,
+    Goals,
+    Mixed,
+}
+
+/// Meaning of the first three second column symbols of an encoding, X, Y and Z by default
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ColumnMapping(pub [Meaning; 3]);
+
+impl ColumnMapping {
+    /// Every mapping where no two symbols have the same meaning
+    pub fn all() -> Vec<ColumnMapping> {
+        let meanings = RPS::ALL
+            .into_iter()
+            .map(Meaning::Move)
+            .chain(RoundResult::ALL.into_iter().map(Meaning::Goal))
+            .collect::<Vec<_>>();
+
+        let mut mappings = Vec::new();
+        for x in &meanings {
+            for y in meanings.iter().filter(|y| *y != x) {
+                for z in meanings.iter().filter(|z| *z != x && *z != y) {
+                    mappings.push(ColumnMapping([*x, *y, *z]));
+                }
+            }
+        }
+        mappings
+    }
+
+    pub fn kind(&self) -> MappingKind {
+        if self.0.iter().all(|m| matches!(m, Meaning::Move(_))) {
+            MappingKind::Moves
+        } else if self.0.iter().all(|m| matches!(m, Meaning::Goal(_))) {
+            MappingKind::Goals
+        } else {
+            MappingKind::Mixed
+        }
+    }
+
+    fn play(&self, &(enemy, symbol): &(RPS, usize)) -> (RPS, RoundResult) {
+        match self.0[symbol] {
+            Meaning::Move(yours) => (yours, yours.against(&enemy)),
+            Meaning::Goal(goal) => (RPS::for_goal(&enemy, &goal), goal),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MappingScore {
+    pub mapping: ColumnMapping,
+    pub score: i32,
+    /// Whether the score is the expected total, if one was given
+    pub matches: bool,
+}
+
+/// Reads the guide as the enemy's move and the position of the second column symbol in `encoding`.
+///
+/// Only the first three second column symbols can be explored, others are unknown symbols.
+pub fn read_guide_with<R>(read: R, encoding: &Encoding) -> Result<Vec<(RPS, usize)>, Error>
+where
+    R: Read,
+{
+    let lines = BufReader::new(read).lines();
+    let lines = lines
+        .map(|line| line.map_err(Error::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    lines
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|line| match encoding.parse_symbols(line)? {
+            (_, position) if position >= 3 => {
+                let symbol = line.split_whitespace().nth(1).unwrap_or_default();
+                Err(Error::UnknownSymbol(symbol.to_string(), line.to_string()))
+            }
+            symbols => Ok(symbols),
+        })
+        .collect()
+}
+
+pub fn explore(
+    guide: &[(RPS, usize)],
+    rules: &ScoringRules,
+    expected: Option<i32>,
+) -> Vec<MappingScore> {
+    ColumnMapping::all()
+        .into_iter()
+        .map(|mapping| {
+            let score = rules.score(guide.iter().map(|symbols| mapping.play(symbols)));
+            MappingScore {
+                mapping,
+                score,
+                matches: expected == Some(score),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RoundResult::{Draw, Lose, Win};
+    use crate::RPS::{Paper, Rock, Scissors};
+    use crate::TEST_STR;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_all() {
+        let mappings = ColumnMapping::all();
+        assert_eq!(mappings.len(), 120);
+
+        let count = |kind| mappings.iter().filter(|m| m.kind() == kind).count();
+        assert_eq!(count(MappingKind::Moves), 6);
+        assert_eq!(count(MappingKind::Goals), 6);
+        assert_eq!(count(MappingKind::Mixed), 108);
+    }
+
+    #[test]
+    fn test_explore() {
+        let encoding = Encoding::default();
+        let guide = read_guide_with(TEST_STR.as_bytes(), &encoding).unwrap();
+        let actual = explore(&guide, &ScoringRules::default(), Some(15));
+
+        let part_1 = ColumnMapping([
+            Meaning::Move(Rock),
+            Meaning::Move(Paper),
+            Meaning::Move(Scissors),
+        ]);
+        let part_2 = ColumnMapping([Meaning::Goal(Lose), Meaning::Goal(Draw), Meaning::Goal(Win)]);
+        let score = |mapping| actual.iter().find(|s| s.mapping == mapping).unwrap();
+        assert_eq!(score(part_1).score, 15);
+        assert!(score(part_1).matches);
+        assert_eq!(score(part_2).score, 12);
+        assert!(!score(part_2).matches);
+    }
+
+    #[test]
+    fn test_explore_encoding() {
+        let encoding =
+            Encoding::from_str("A=rock X=paper/lose Y=rock/draw Z=scissors/win").unwrap();
+        let guide = read_guide_with("A X\n".as_bytes(), &encoding).unwrap();
+        assert_eq!(guide, vec![(Rock, 0)]);
+
+        let actual = explore(&guide, &ScoringRules::default(), None);
+        let mapping = ColumnMapping([
+            Meaning::Move(Paper),
+            Meaning::Move(Rock),
+            Meaning::Move(Scissors),
+        ]);
+        let score = actual.iter().find(|s| s.mapping == mapping).unwrap();
+        assert_eq!(score.score, 8);
+
+        let encoding = encoding.with_response("W", Rock, Draw);
+        assert!(matches!(
+            read_guide_with("A W\n".as_bytes(), &encoding),
+            Err(Error::UnknownSymbol(symbol, _)) if symbol == "W"
+        ));
+    }
+}
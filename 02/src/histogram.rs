@@ -0,0 +1,139 @@
+use crate::{CyclicMove, Encoding, Error, Round, RoundResult, RPS};
+use std::io::{BufRead, BufReader, Read};
+
+const KINDS: usize = 27;
+
+/// Number of rounds of each kind, for guides too large to be kept in memory.
+///
+/// A round only depends on the enemy's move, your move and the goal, so
+/// there are at most 27 kinds of rounds (9 with the default encoding).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RoundHistogram {
+    counts: [u64; KINDS],
+}
+
+impl RoundHistogram {
+    pub fn add(&mut self, round: &Round) {
+        self.add_count(round, 1);
+    }
+
+    pub fn add_count(&mut self, round: &Round, count: u64) {
+        self.counts[kind(round)] += count;
+    }
+
+    pub fn count(&self, round: &Round) -> u64 {
+        self.counts[kind(round)]
+    }
+
+    pub fn len(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn score_1(&self) -> i64 {
+        self.dot(Round::score_1)
+    }
+
+    pub fn score_2(&self) -> i64 {
+        self.dot(Round::score_2)
+    }
+
+    fn dot<F>(&self, score: F) -> i64
+    where
+        F: Fn(&Round) -> i32,
+    {
+        all_rounds()
+            .map(|round| self.counts[kind(&round)] as i64 * score(&round) as i64)
+            .sum()
+    }
+}
+
+impl<'a> FromIterator<&'a Round> for RoundHistogram {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Round>,
+    {
+        let mut histogram = RoundHistogram::default();
+        for round in iter {
+            histogram.add(round);
+        }
+        histogram
+    }
+}
+
+fn kind(round: &Round) -> usize {
+    let goal = match round.goal() {
+        RoundResult::Win => 0,
+        RoundResult::Draw => 1,
+        RoundResult::Lose => 2,
+    };
+    round.enemy().index() * 9 + round.yours().index() * 3 + goal
+}
+
+fn all_rounds() -> impl Iterator<Item = Round> {
+    RPS::ALL.into_iter().flat_map(|enemy| {
+        RPS::ALL.into_iter().flat_map(move |yours| {
+            RoundResult::ALL
+                .into_iter()
+                .map(move |goal| Round::new(enemy, yours, goal))
+        })
+    })
+}
+
+pub fn read_histogram<R>(read: R) -> Result<RoundHistogram, Error>
+where
+    R: Read,
+{
+    read_histogram_with(read, &Encoding::default())
+}
+
+/// Same as `read_input_with`, but only counts the rounds instead of keeping them
+pub fn read_histogram_with<R>(read: R, encoding: &Encoding) -> Result<RoundHistogram, Error>
+where
+    R: Read,
+{
+    let mut reader = BufReader::new(read);
+    let mut histogram = RoundHistogram::default();
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            histogram.add(&encoding.parse_line(trimmed)?);
+        }
+        line.clear();
+    }
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_score_1, compute_score_2, read_input};
+
+    const TEST_STR: &str = "A Y\nB X\nC Z\nA Y\n\nC X\n";
+
+    #[test]
+    fn test_histogram() {
+        let rounds = read_input(TEST_STR.as_bytes()).unwrap();
+        let actual = read_histogram(TEST_STR.as_bytes()).unwrap();
+        assert_eq!(actual, rounds.iter().collect::<RoundHistogram>());
+        assert_eq!(actual.len(), 5);
+        assert_eq!(actual.count(&rounds[0]), 2);
+
+        assert_eq!(actual.score_1(), compute_score_1(&rounds) as i64);
+        assert_eq!(actual.score_2(), compute_score_2(&rounds) as i64);
+    }
+
+    #[test]
+    fn test_large_histogram() {
+        let round = Round::new(RPS::Rock, RPS::Paper, RoundResult::Draw);
+        let mut histogram = RoundHistogram::default();
+        histogram.add_count(&round, 1_000_000_000);
+        assert_eq!(histogram.score_1(), 8_000_000_000);
+        assert_eq!(histogram.score_2(), 4_000_000_000);
+    }
+}
@@ -1,25 +1,58 @@
 use self::RoundResult::{Draw, Lose, Win};
 use self::RPS::{Paper, Rock, Scissors};
+use std::fmt::{Display, Formatter};
 use std::io::{BufRead, BufReader, Error as IoError, Read};
 use thiserror::Error;
 
+mod cyclic;
+mod encoding;
+mod explorer;
+mod histogram;
+mod matrix;
+mod optimizer;
+mod scoring;
+mod tournament;
+mod trace;
+
+pub use cyclic::{CyclicGame, CyclicMove, RPSLS};
+pub use encoding::Encoding;
+pub use explorer::{explore, read_guide_with, ColumnMapping, MappingKind, MappingScore, Meaning};
+pub use histogram::{read_histogram, read_histogram_with, RoundHistogram};
+pub use matrix::{enemy_distribution, Equilibrium, MatrixGame};
+pub use optimizer::{check_guide, optimize, BestResponse, Constraints, GuideCheck};
+pub use scoring::{ScoringRules, StreakBonus};
+pub use tournament::{
+    FrequencyPlayer, GuidePlayer, MarkovPlayer, Player, RandomPlayer, Rng, Standing, Tournament,
+    WinStayLoseShiftPlayer,
+};
+pub use trace::{export_trace, trace, Part, RoundTrace, TraceFormat};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("I/O error")]
     IO(#[from] IoError),
     #[error("Invalid input {}", .0)]
     Parse(String),
+    #[error("Unknown symbol {} in {}", .0, .1)]
+    UnknownSymbol(String, String),
+    #[error("Invalid encoding entry {}", .0)]
+    Encoding(String),
+    #[error("Too many states {} to optimize", .0)]
+    TooManyStates(usize),
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum RPS {
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RPS {
     Rock,
     Paper,
     Scissors,
 }
 
 impl RPS {
-    fn score(&self) -> i32 {
+    pub const ALL: [RPS; 3] = [Rock, Paper, Scissors];
+
+    pub fn score(&self) -> i32 {
         match self {
             Rock => 1,
             Paper => 2,
@@ -28,15 +61,43 @@ impl RPS {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum RoundResult {
+impl Display for RPS {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rock => write!(f, "rock"),
+            Paper => write!(f, "paper"),
+            Scissors => write!(f, "scissors"),
+        }
+    }
+}
+
+impl CyclicMove for RPS {
+    const GAME: CyclicGame = CyclicGame::ROCK_PAPER_SCISSORS;
+
+    fn index(&self) -> usize {
+        match self {
+            Rock => 0,
+            Paper => 1,
+            Scissors => 2,
+        }
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        RPS::ALL.get(index).copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RoundResult {
     Win,
     Draw,
     Lose,
 }
 
 impl RoundResult {
-    fn score(&self) -> i32 {
+    pub const ALL: [RoundResult; 3] = [Win, Draw, Lose];
+
+    pub fn score(&self) -> i32 {
         match self {
             Win => 6,
             Draw => 3,
@@ -45,7 +106,17 @@ impl RoundResult {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+impl Display for RoundResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Win => write!(f, "win"),
+            Draw => write!(f, "draw"),
+            Lose => write!(f, "lose"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Round {
     enemy: RPS,
     yours: RPS,
@@ -53,36 +124,52 @@ pub struct Round {
 }
 
 impl Round {
-    fn new(enemy: RPS, yours: RPS, goal: RoundResult) -> Self {
+    pub fn new(enemy: RPS, yours: RPS, goal: RoundResult) -> Self {
         Round { enemy, yours, goal }
     }
 
-    fn get_round_result(&self) -> RoundResult {
-        match (&self.enemy, &self.yours) {
-            (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => Win,
-            (Rock, Rock) | (Paper, Paper) | (Scissors, Scissors) => Draw,
-            (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => Lose,
-        }
+    pub fn enemy(&self) -> RPS {
+        self.enemy
     }
 
-    fn get_your_move(&self) -> RPS {
-        match (&self.enemy, &self.goal) {
-            (Rock, Draw) | (Paper, Lose) | (Scissors, Win) => Rock,
-            (Rock, Win) | (Paper, Draw) | (Scissors, Lose) => Paper,
-            (Rock, Lose) | (Paper, Win) | (Scissors, Draw) => Scissors,
-        }
+    pub fn yours(&self) -> RPS {
+        self.yours
     }
 
-    fn score_1(&self) -> i32 {
+    pub fn goal(&self) -> RoundResult {
+        self.goal
+    }
+
+    pub fn get_round_result(&self) -> RoundResult {
+        self.yours.against(&self.enemy)
+    }
+
+    pub fn get_your_move(&self) -> RPS {
+        RPS::for_goal(&self.enemy, &self.goal)
+    }
+
+    pub fn score_1(&self) -> i32 {
         self.get_round_result().score() + self.yours.score()
     }
 
-    fn score_2(&self) -> i32 {
+    pub fn score_2(&self) -> i32 {
         self.goal.score() + self.get_your_move().score()
     }
 }
 
+/// Score of a round for every move, indexed by the enemy's move then yours, in `RPS::ALL` order
+pub fn score_table() -> [[i32; 3]; 3] {
+    RPS::ALL.map(|enemy| RPS::ALL.map(|yours| yours.against(&enemy).score() + yours.score()))
+}
+
 pub fn read_input<R>(read: R) -> Result<Vec<Round>, Error>
+where
+    R: Read,
+{
+    read_input_with(read, &Encoding::default())
+}
+
+pub fn read_input_with<R>(read: R, encoding: &Encoding) -> Result<Vec<Round>, Error>
 where
     R: Read,
 {
@@ -94,26 +181,11 @@ where
         .iter()
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
-        .map(read_line)
+        .map(|line| encoding.parse_line(line))
         .collect::<Result<Vec<_>, _>>()?;
     Ok(rounds)
 }
 
-fn read_line(line: &str) -> Result<Round, Error> {
-    match line.trim() {
-        "A X" => Ok(Round::new(Rock, Rock, Lose)),
-        "A Y" => Ok(Round::new(Rock, Paper, Draw)),
-        "A Z" => Ok(Round::new(Rock, Scissors, Win)),
-        "B X" => Ok(Round::new(Paper, Rock, Lose)),
-        "B Y" => Ok(Round::new(Paper, Paper, Draw)),
-        "B Z" => Ok(Round::new(Paper, Scissors, Win)),
-        "C X" => Ok(Round::new(Scissors, Rock, Lose)),
-        "C Y" => Ok(Round::new(Scissors, Paper, Draw)),
-        "C Z" => Ok(Round::new(Scissors, Scissors, Win)),
-        _ => Err(Error::Parse(line.to_string())),
-    }
-}
-
 pub fn compute_score_1(rounds: &[Round]) -> i32 {
     rounds.iter().map(|round| round.score_1()).sum()
 }
@@ -122,17 +194,35 @@ pub fn compute_score_2(rounds: &[Round]) -> i32 {
     rounds.iter().map(|round| round.score_2()).sum()
 }
 
+pub fn compute_score_1_with(rounds: &[Round], rules: &ScoringRules) -> i32 {
+    rules.score(
+        rounds
+            .iter()
+            .map(|round| (round.yours, round.get_round_result())),
+    )
+}
+
+pub fn compute_score_2_with(rounds: &[Round], rules: &ScoringRules) -> i32 {
+    rules.score(
+        rounds
+            .iter()
+            .map(|round| (round.get_your_move(), round.goal)),
+    )
+}
+
+#[cfg(test)]
+pub(crate) const TEST_STR: &str = r#"A Y
+B X
+C Z
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_parse() {
-        let test_str = r#"A Y
-B X
-C Z
-"#;
-        let actual = read_input(test_str.as_bytes()).unwrap();
+        let actual = read_input(TEST_STR.as_bytes()).unwrap();
         let expected = vec![
             Round::new(Rock, Paper, Draw),
             Round::new(Paper, Rock, Lose),
@@ -146,4 +236,33 @@ C Z
         let score = compute_score_2(&actual);
         assert_eq!(score, 12);
     }
+
+    #[test]
+    fn test_scoring_rules() {
+        let rounds = read_input(TEST_STR.as_bytes()).unwrap();
+        let rules = ScoringRules::default();
+        assert_eq!(compute_score_1_with(&rounds, &rules), 15);
+        assert_eq!(compute_score_2_with(&rounds, &rules), 12);
+
+        let rules = ScoringRules::new([0, 0, 0], [3, 1, 0]).with_streak_bonus(1, 2);
+        assert_eq!(compute_score_1_with(&rounds, &rules), 6);
+        assert_eq!(compute_score_2_with(&rounds, &rules), 6);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let actual = read_input("A Y\nD X\n".as_bytes());
+        assert!(matches!(actual, Err(Error::UnknownSymbol(symbol, _)) if symbol == "D"));
+    }
+
+    #[test]
+    fn test_game() {
+        assert_eq!(Paper.against(&Rock), Win);
+        assert_eq!(Rock.against(&Paper), Lose);
+        assert_eq!(Scissors.against(&Scissors), Draw);
+        assert_eq!(RPS::for_goal(&Rock, &Lose), Scissors);
+
+        let table = score_table();
+        assert_eq!(table, [[4, 8, 3], [1, 5, 9], [7, 2, 6]]);
+    }
 }
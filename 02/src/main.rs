@@ -1,13 +1,32 @@
-use advent2022_02::{compute_score_1, compute_score_2, read_input};
+use advent2022_02::{
+    compute_score_1, compute_score_2, export_trace, read_input, trace, Part, ScoringRules,
+    TraceFormat,
+};
+use advent2022_utils::find_option;
 use anyhow::Result;
+use std::env;
 use std::fs::File;
 use std::path::Path;
+use std::str::FromStr;
 
 fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
     let path = Path::new("resources/02/input");
     let file = File::open(path)?;
     let rounds = read_input(file)?;
 
+    // Print the details of every round instead of the scores
+    if let Some(part) = find_option(&args, "--trace")? {
+        let part = Part::from_str(part)?;
+        let format = match find_option(&args, "--format")? {
+            Some(format) => TraceFormat::from_str(format)?,
+            None => TraceFormat::Csv,
+        };
+        let traces = trace(&rounds, part, &ScoringRules::default());
+        print!("{}", export_trace(&traces, format));
+        return Ok(());
+    }
+
     let score = compute_score_1(&rounds);
     println!("{score}");
 
@@ -0,0 +1,229 @@
+use crate::{CyclicMove, Round, ScoringRules, RPS};
+
+const EPSILON: f64 = 1e-9;
+
+/// A two player matrix game, with the payoffs of the row player.
+///
+/// Rows are your moves, and columns the enemy's moves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixGame {
+    payoffs: Vec<Vec<f64>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Equilibrium {
+    pub strategy: Vec<f64>,
+    pub enemy_strategy: Vec<f64>,
+    pub value: f64,
+}
+
+impl MatrixGame {
+    /// Returns `None` if the matrix is empty or not rectangular
+    pub fn new(payoffs: Vec<Vec<f64>>) -> Option<Self> {
+        let columns = payoffs.first()?.len();
+        if columns == 0 || payoffs.iter().any(|row| row.len() != columns) {
+            None
+        } else {
+            Some(MatrixGame { payoffs })
+        }
+    }
+
+    /// Your score for each of your moves against each of the enemy's moves, in `RPS::ALL` order
+    pub fn from_rules(rules: &ScoringRules) -> Self {
+        let payoffs = RPS::ALL
+            .iter()
+            .map(|yours| {
+                RPS::ALL
+                    .iter()
+                    .map(|enemy| rules.round_score(yours, &yours.against(enemy)) as f64)
+                    .collect()
+            })
+            .collect();
+        MatrixGame { payoffs }
+    }
+
+    pub fn payoff(&self, yours: usize, enemy: usize) -> f64 {
+        self.payoffs[yours][enemy]
+    }
+
+    pub fn expected_score(&self, strategy: &[f64], enemy_strategy: &[f64]) -> f64 {
+        self.payoffs
+            .iter()
+            .zip(strategy)
+            .map(|(row, p)| {
+                let row_score = row
+                    .iter()
+                    .zip(enemy_strategy)
+                    .map(|(a, q)| a * q)
+                    .sum::<f64>();
+                p * row_score
+            })
+            .sum()
+    }
+
+    /// Solves the game as a zero-sum game: you maximize your payoff, and the enemy minimizes it.
+    ///
+    /// This uses the simplex method on the enemy's linear program, the payoffs being shifted
+    /// to be positive. Your strategy is read from the dual of the program.
+    pub fn solve(&self) -> Equilibrium {
+        let rows = self.payoffs.len();
+        let columns = self.payoffs[0].len();
+        let min = self
+            .payoffs
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let shift = 1.0 - min;
+
+        // maximize sum(y) under A y <= 1 and y >= 0, with one slack variable per row
+        let width = columns + rows + 1;
+        let mut tableau = self
+            .payoffs
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut line = vec![0.0; width];
+                for (j, payoff) in row.iter().enumerate() {
+                    line[j] = payoff + shift;
+                }
+                line[columns + i] = 1.0;
+                line[width - 1] = 1.0;
+                line
+            })
+            .collect::<Vec<_>>();
+        let mut objective = vec![0.0; width];
+        objective[..columns].fill(-1.0);
+        let mut basis = (columns..columns + rows).collect::<Vec<_>>();
+
+        // Bland's rule: pick the first improving column, so that we never cycle
+        while let Some(entering) = (0..width - 1).find(|j| objective[*j] < -EPSILON) {
+            let leaving = (0..rows)
+                .filter(|i| tableau[*i][entering] > EPSILON)
+                .min_by(|a, b| {
+                    let ratio_a = tableau[*a][width - 1] / tableau[*a][entering];
+                    let ratio_b = tableau[*b][width - 1] / tableau[*b][entering];
+                    ratio_a.total_cmp(&ratio_b).then(basis[*a].cmp(&basis[*b]))
+                });
+            // Cannot happen, the program is bounded since all payoffs are positive
+            let leaving = match leaving {
+                Some(leaving) => leaving,
+                None => break,
+            };
+
+            let pivot = tableau[leaving][entering];
+            tableau[leaving]
+                .iter_mut()
+                .for_each(|value| *value /= pivot);
+            let pivot_row = tableau[leaving].clone();
+            for (i, row) in tableau.iter_mut().enumerate() {
+                if i != leaving {
+                    let factor = row[entering];
+                    row.iter_mut()
+                        .zip(&pivot_row)
+                        .for_each(|(value, p)| *value -= factor * p);
+                }
+            }
+            let factor = objective[entering];
+            objective
+                .iter_mut()
+                .zip(&pivot_row)
+                .for_each(|(value, p)| *value -= factor * p);
+            basis[leaving] = entering;
+        }
+
+        let total = objective[width - 1];
+        let mut enemy_strategy = vec![0.0; columns];
+        for (i, variable) in basis.iter().enumerate() {
+            if *variable < columns {
+                enemy_strategy[*variable] = tableau[i][width - 1] / total;
+            }
+        }
+        let strategy = objective[columns..columns + rows]
+            .iter()
+            .map(|dual| dual / total)
+            .collect();
+
+        Equilibrium {
+            strategy,
+            enemy_strategy,
+            value: 1.0 / total - shift,
+        }
+    }
+}
+
+/// How often the enemy plays each move in the guide, in `RPS::ALL` order
+pub fn enemy_distribution(rounds: &[Round]) -> Option<[f64; 3]> {
+    if rounds.is_empty() {
+        return None;
+    }
+
+    let mut counts = [0; 3];
+    for round in rounds {
+        counts[round.enemy().index()] += 1;
+    }
+    Some(counts.map(|count| count as f64 / rounds.len() as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_expected_score() {
+        let rounds = read_input("A Y\nB X\nC Z\nA X\n".as_bytes()).unwrap();
+        let distribution = enemy_distribution(&rounds).unwrap();
+        assert_close(&distribution, &[0.5, 0.25, 0.25]);
+
+        let game = MatrixGame::from_rules(&ScoringRules::default());
+        let paper = [0.0, 1.0, 0.0];
+        assert_close(&[game.expected_score(&paper, &distribution)], &[5.75]);
+    }
+
+    #[test]
+    fn test_solve_rps() {
+        let payoffs = vec![
+            vec![0.0, -1.0, 1.0],
+            vec![1.0, 0.0, -1.0],
+            vec![-1.0, 1.0, 0.0],
+        ];
+        let actual = MatrixGame::new(payoffs).unwrap().solve();
+        let third = 1.0 / 3.0;
+        assert_close(&actual.strategy, &[third, third, third]);
+        assert_close(&actual.enemy_strategy, &[third, third, third]);
+        assert_close(&[actual.value], &[0.0]);
+    }
+
+    #[test]
+    fn test_solve() {
+        let game = MatrixGame::new(vec![vec![3.0, -1.0], vec![-2.0, 1.0]]).unwrap();
+        let actual = game.solve();
+        assert_close(&actual.strategy, &[3.0 / 7.0, 4.0 / 7.0]);
+        assert_close(&actual.enemy_strategy, &[2.0 / 7.0, 5.0 / 7.0]);
+        assert_close(&[actual.value], &[1.0 / 7.0]);
+
+        // Neither player can do better by changing its strategy
+        let game = MatrixGame::from_rules(&ScoringRules::default());
+        let actual = game.solve();
+        for i in 0..3 {
+            let mut pure = [0.0; 3];
+            pure[i] = 1.0;
+            assert!(game.expected_score(&pure, &actual.enemy_strategy) <= actual.value + 1e-6);
+            assert!(game.expected_score(&actual.strategy, &pure) >= actual.value - 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(MatrixGame::new(vec![]).is_none());
+        assert!(MatrixGame::new(vec![vec![1.0], vec![1.0, 2.0]]).is_none());
+    }
+}
@@ -0,0 +1,563 @@
+use crate::{
+    compute_score_1_with, compute_score_2_with, CyclicMove, Error, Round, ScoringRules, RPS,
+};
+use std::ops::RangeInclusive;
+
+// States kept over all rounds for backtracking, about 128 MB of back-pointers
+const MAX_STEPS: usize = 1 << 24;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Constraints {
+    /// Each move can be used at most this many times
+    pub max_uses: Option<usize>,
+    /// The same move cannot be played more than this many times in a row
+    pub max_consecutive: Option<usize>,
+}
+
+impl Constraints {
+    pub fn is_satisfied_by(&self, moves: &[RPS]) -> bool {
+        let uses_ok = match self.max_uses {
+            Some(max_uses) => RPS::ALL
+                .iter()
+                .all(|m| moves.iter().filter(|yours| *yours == m).count() <= max_uses),
+            None => true,
+        };
+        let consecutive_ok = match self.max_consecutive {
+            Some(max_consecutive) => moves
+                .chunk_by(|a, b| a == b)
+                .all(|run| run.len() <= max_consecutive),
+            None => true,
+        };
+        uses_ok && consecutive_ok
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BestResponse {
+    pub moves: Vec<RPS>,
+    pub score: i32,
+}
+
+/// Compares the scores of both interpretations of a guide with the best possible response
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuideCheck {
+    pub score_1: i32,
+    pub satisfies_1: bool,
+    pub score_2: i32,
+    pub satisfies_2: bool,
+    pub optimum: Option<BestResponse>,
+}
+
+/// Finds the moves that maximize the total score against the enemy's moves.
+///
+/// When only the uses of each move are constrained, the order of the moves does not matter,
+/// and this is a small transportation problem. Otherwise, this is an exact dynamic
+/// programming solution over the rounds, whose time and memory grow with the number of rounds
+/// times the number of states per round. When the uses are constrained too, there are
+/// O(max_uses²) states per round, so the total is cubic in the number of rounds, and
+/// `Error::TooManyStates` is returned above a fixed budget.
+/// Returns `None` if the constraints cannot be satisfied.
+pub fn optimize(
+    enemies: &[RPS],
+    constraints: &Constraints,
+    rules: &ScoringRules,
+) -> Result<Option<BestResponse>, Error> {
+    match constraints {
+        Constraints {
+            max_uses: Some(max_uses),
+            max_consecutive: None,
+        } if rules.streak_bonus().is_none() => Ok(optimize_uses(enemies, *max_uses, rules)),
+        _ => optimize_rounds(enemies, constraints, rules),
+    }
+}
+
+// What we need to remember about the moves played so far. Parts of the state that are not
+// constrained keep their default value, to keep the number of states low. The number of
+// scissors is the number of rounds played minus the rocks and papers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct State {
+    rocks: usize,
+    papers: usize,
+    // 0 before the first round, then 1 + the index of the last move
+    last: usize,
+    run: usize,
+    streak: usize,
+}
+
+// Reachable states after some rounds, so that they can be stored in flat arrays. Only the
+// numbers of rocks and papers that fit in `max_uses` are kept, with the position of the first
+// one for each number of rocks.
+struct StateSpace {
+    uses: Vec<(usize, usize)>,
+    rows: Vec<usize>,
+    lasts: usize,
+    runs: usize,
+    streaks: usize,
+}
+
+impl StateSpace {
+    fn new(rounds: usize, constraints: &Constraints, rules: &ScoringRules) -> Self {
+        let mut uses = Vec::new();
+        let mut rows = Vec::new();
+        match constraints.max_uses {
+            Some(max_uses) => {
+                for rocks in 0..=max_uses.min(rounds) {
+                    rows.push(uses.len());
+                    uses.extend(
+                        papers_range(rounds, rocks, max_uses).map(|papers| (rocks, papers)),
+                    );
+                }
+            }
+            None => {
+                rows.push(0);
+                uses.push((0, 0));
+            }
+        }
+        let (lasts, runs, streaks) = Self::sizes(rounds, constraints, rules);
+        StateSpace {
+            uses,
+            rows,
+            lasts,
+            runs,
+            streaks,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.uses.len() * self.lasts * self.runs * self.streaks
+    }
+
+    // Same as `len`, without building the space
+    fn count(rounds: usize, constraints: &Constraints, rules: &ScoringRules) -> usize {
+        let uses = match constraints.max_uses {
+            Some(max_uses) => (0..=max_uses.min(rounds))
+                .map(|rocks| papers_range(rounds, rocks, max_uses).count())
+                .sum(),
+            None => 1,
+        };
+        let (lasts, runs, streaks) = Self::sizes(rounds, constraints, rules);
+        uses * lasts * runs * streaks
+    }
+
+    // Number of values of the last move, run and streak
+    fn sizes(
+        rounds: usize,
+        constraints: &Constraints,
+        rules: &ScoringRules,
+    ) -> (usize, usize, usize) {
+        let (lasts, runs) = match constraints.max_consecutive {
+            Some(max_consecutive) => (4, max_consecutive.min(rounds) + 1),
+            None => (1, 1),
+        };
+        // Streaks stop counting at the bonus length
+        let streaks = rules
+            .streak_bonus()
+            .map_or(1, |bonus| bonus.length.max(1) + 1);
+        (lasts, runs, streaks)
+    }
+
+    // Only valid for reachable states
+    fn index(&self, state: &State, max_uses: Option<usize>, rounds: usize) -> usize {
+        let first = max_uses.map_or(0, |max_uses| {
+            *papers_range(rounds, state.rocks, max_uses).start()
+        });
+        let index = self.rows[state.rocks] + state.papers - first;
+        let index = index * self.lasts + state.last;
+        let index = index * self.runs + state.run;
+        index * self.streaks + state.streak
+    }
+
+    fn state(&self, index: usize) -> State {
+        let (rocks, papers) = self.uses[index / (self.streaks * self.runs * self.lasts)];
+        State {
+            streak: index % self.streaks,
+            run: index / self.streaks % self.runs,
+            last: index / (self.streaks * self.runs) % self.lasts,
+            papers,
+            rocks,
+        }
+    }
+}
+
+// Numbers of papers such that no move is used more than `max_uses` times
+fn papers_range(rounds: usize, rocks: usize, max_uses: usize) -> RangeInclusive<usize> {
+    let left = rounds - rocks;
+    left.saturating_sub(max_uses)..=left.min(max_uses)
+}
+
+fn optimize_rounds(
+    enemies: &[RPS],
+    constraints: &Constraints,
+    rules: &ScoringRules,
+) -> Result<Option<BestResponse>, Error> {
+    let mut states = 0;
+    for rounds in 1..=enemies.len() {
+        states += StateSpace::count(rounds, constraints, rules);
+        if states > MAX_STEPS {
+            return Err(Error::TooManyStates(states));
+        }
+    }
+
+    let max_uses = constraints.max_uses;
+    let mut space = StateSpace::new(0, constraints, rules);
+    let mut scores = vec![None; space.len()];
+    scores[space.index(&State::default(), max_uses, 0)] = Some(0);
+
+    // Previous state and move leading to each state, for every round, with the
+    // offset of each round in this array
+    let mut steps = Vec::with_capacity(states);
+    let mut offsets = Vec::with_capacity(enemies.len());
+    for (round, enemy) in enemies.iter().enumerate() {
+        let next_space = StateSpace::new(round + 1, constraints, rules);
+        let mut next_scores = vec![None; next_space.len()];
+        let offset = steps.len();
+        offsets.push(offset);
+        steps.resize(offset + next_space.len(), None);
+
+        for (index, score) in scores.iter().enumerate() {
+            let score = match score {
+                Some(score) => *score,
+                None => continue,
+            };
+            let state = space.state(index);
+            for yours in RPS::ALL {
+                let next = match next_state(&state, round, yours, enemy, constraints, rules) {
+                    Some(next) => next,
+                    None => continue,
+                };
+
+                let result = yours.against(enemy);
+                let score = score + rules.round_score(&yours, &result) + rules.bonus(next.streak);
+                let next = next_space.index(&next, max_uses, round + 1);
+                if next_scores[next].is_none_or(|best| score > best) {
+                    next_scores[next] = Some(score);
+                    steps[offset + next] = Some((index as u32, yours));
+                }
+            }
+        }
+        scores = next_scores;
+        space = next_space;
+    }
+
+    let best = scores
+        .iter()
+        .enumerate()
+        .filter_map(|(index, score)| score.map(|score| (index, score)))
+        .max_by_key(|(_, score)| *score);
+    let (mut index, score) = match best {
+        Some(best) => best,
+        None => return Ok(None),
+    };
+
+    // Backtrack to find the moves
+    let mut moves = Vec::with_capacity(enemies.len());
+    for offset in offsets.into_iter().rev() {
+        let (previous, yours) = steps[offset + index].expect("Reached states have a step");
+        moves.push(yours);
+        index = previous as usize;
+    }
+    moves.reverse();
+
+    Ok(Some(BestResponse { moves, score }))
+}
+
+fn next_state(
+    state: &State,
+    round: usize,
+    yours: RPS,
+    enemy: &RPS,
+    constraints: &Constraints,
+    rules: &ScoringRules,
+) -> Option<State> {
+    let index = yours.index();
+    let mut next = State::default();
+
+    if let Some(max_uses) = constraints.max_uses {
+        next.rocks = state.rocks + usize::from(index == 0);
+        next.papers = state.papers + usize::from(index == 1);
+        let scissors = round + 1 - next.rocks - next.papers;
+        if next.rocks.max(next.papers).max(scissors) > max_uses {
+            return None;
+        }
+    }
+
+    if let Some(max_consecutive) = constraints.max_consecutive {
+        next.last = index + 1;
+        next.run = if state.last == index + 1 {
+            state.run + 1
+        } else {
+            1
+        };
+        if next.run > max_consecutive {
+            return None;
+        }
+    }
+
+    next.streak = rules.next_streak(state.streak, &yours.against(enemy));
+
+    Some(next)
+}
+
+// Only the number of times each move answers each enemy move matters: this is a min-cost
+// flow from the enemy moves, with their counts as supplies, to your moves, limited by `max_uses`
+fn optimize_uses(enemies: &[RPS], max_uses: usize, rules: &ScoringRules) -> Option<BestResponse> {
+    let mut counts = [0; 3];
+    for enemy in enemies {
+        counts[enemy.index()] += 1;
+    }
+
+    // Nodes are the source, the enemy moves, your moves and the sink
+    let (source, sink) = (0, 7);
+    let rounds = enemies.len() as i64;
+    let mut flow = Flow::new(8);
+    let mut answers = [[0; 3]; 3];
+    for enemy in RPS::ALL {
+        flow.add_edge(source, 1 + enemy.index(), counts[enemy.index()], 0);
+        for yours in RPS::ALL {
+            let score = rules.round_score(&yours, &yours.against(&enemy));
+            answers[enemy.index()][yours.index()] =
+                flow.add_edge(1 + enemy.index(), 4 + yours.index(), rounds, -score as i64);
+        }
+    }
+    for yours in RPS::ALL {
+        flow.add_edge(4 + yours.index(), sink, rounds.min(max_uses as i64), 0);
+    }
+
+    if flow.run(source, sink) < rounds {
+        return None;
+    }
+
+    let mut answers = answers.map(|row| row.map(|edge| flow.flow(edge)));
+    let moves = enemies
+        .iter()
+        .map(|enemy| {
+            let row = &mut answers[enemy.index()];
+            let yours = RPS::ALL.into_iter().find(|yours| row[yours.index()] > 0)?;
+            row[yours.index()] -= 1;
+            Some(yours)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let score = rules.score(moves.iter().zip(enemies).map(|(y, e)| (*y, y.against(e))));
+    Some(BestResponse { moves, score })
+}
+
+struct FlowEdge {
+    to: usize,
+    capacity: i64,
+    cost: i64,
+}
+
+// Successive shortest paths, on graphs small enough for Bellman-Ford
+struct Flow {
+    nodes: usize,
+    // Every edge is followed by its reverse edge
+    edges: Vec<FlowEdge>,
+}
+
+impl Flow {
+    fn new(nodes: usize) -> Self {
+        Flow {
+            nodes,
+            edges: Vec::new(),
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, capacity: i64, cost: i64) -> usize {
+        let edge = self.edges.len();
+        self.edges.push(FlowEdge { to, capacity, cost });
+        self.edges.push(FlowEdge {
+            to: from,
+            capacity: 0,
+            cost: -cost,
+        });
+        edge
+    }
+
+    fn flow(&self, edge: usize) -> i64 {
+        self.edges[edge ^ 1].capacity
+    }
+
+    // Sends as much flow as possible, with the lowest cost. Returns the amount sent.
+    fn run(&mut self, source: usize, sink: usize) -> i64 {
+        let mut total = 0;
+        loop {
+            let mut distances = vec![None; self.nodes];
+            let mut previous = vec![None; self.nodes];
+            distances[source] = Some(0);
+            for _ in 1..self.nodes {
+                let mut changed = false;
+                for (id, edge) in self.edges.iter().enumerate() {
+                    let from = self.edges[id ^ 1].to;
+                    let distance = match distances[from] {
+                        Some(distance) if edge.capacity > 0 => distance + edge.cost,
+                        _ => continue,
+                    };
+                    if distances[edge.to].is_none_or(|d| distance < d) {
+                        distances[edge.to] = Some(distance);
+                        previous[edge.to] = Some(id);
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+            if distances[sink].is_none() {
+                return total;
+            }
+
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some(id) = previous[node] {
+                path.push(id);
+                node = self.edges[id ^ 1].to;
+            }
+            let amount = path
+                .iter()
+                .map(|id| self.edges[*id].capacity)
+                .min()
+                .unwrap_or(0);
+            for id in path {
+                self.edges[id].capacity -= amount;
+                self.edges[id ^ 1].capacity += amount;
+            }
+            total += amount;
+        }
+    }
+}
+
+pub fn check_guide(
+    rounds: &[Round],
+    constraints: &Constraints,
+    rules: &ScoringRules,
+) -> Result<GuideCheck, Error> {
+    let enemies = rounds.iter().map(|round| round.enemy()).collect::<Vec<_>>();
+    let moves_1 = rounds.iter().map(|round| round.yours()).collect::<Vec<_>>();
+    let moves_2 = rounds
+        .iter()
+        .map(|round| round.get_your_move())
+        .collect::<Vec<_>>();
+
+    Ok(GuideCheck {
+        score_1: compute_score_1_with(rounds, rules),
+        satisfies_1: constraints.is_satisfied_by(&moves_1),
+        score_2: compute_score_2_with(rounds, rules),
+        satisfies_2: constraints.is_satisfied_by(&moves_2),
+        optimum: optimize(&enemies, constraints, rules)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RPS::{Paper, Rock};
+    use crate::{read_input, Rng, TEST_STR};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_optimize() {
+        let enemies = [Rock, Rock, Rock];
+        let rules = ScoringRules::default();
+
+        let actual = optimize(&enemies, &Constraints::default(), &rules)
+            .unwrap()
+            .unwrap();
+        assert_eq!(actual.moves, vec![Paper, Paper, Paper]);
+        assert_eq!(actual.score, 24);
+
+        let constraints = Constraints {
+            max_uses: None,
+            max_consecutive: Some(2),
+        };
+        let actual = optimize(&enemies, &constraints, &rules).unwrap().unwrap();
+        assert!(constraints.is_satisfied_by(&actual.moves));
+        assert_eq!(actual.score, 20);
+
+        let constraints = Constraints {
+            max_uses: Some(1),
+            max_consecutive: None,
+        };
+        let actual = optimize(&enemies, &constraints, &rules).unwrap().unwrap();
+        assert!(constraints.is_satisfied_by(&actual.moves));
+        assert_eq!(actual.score, 15);
+
+        assert!(optimize(&[Rock; 4], &constraints, &rules)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_optimize_uses() {
+        let rules = ScoringRules::default();
+        let mut rng = Rng::new(3);
+        let enemies = (0..30).map(|_| rng.random_move()).collect::<Vec<_>>();
+        let constraints = Constraints {
+            max_uses: Some(12),
+            max_consecutive: None,
+        };
+
+        let actual = optimize_uses(&enemies, 12, &rules).unwrap();
+        let expected = optimize_rounds(&enemies, &constraints, &rules)
+            .unwrap()
+            .unwrap();
+        assert!(constraints.is_satisfied_by(&actual.moves));
+        assert_eq!(actual.score, expected.score);
+        assert_eq!(optimize_uses(&enemies, 9, &rules), None);
+    }
+
+    #[test]
+    fn test_optimize_large() {
+        let rules = ScoringRules::default();
+        let mut rng = Rng::new(7);
+        let enemies = (0..2500).map(|_| rng.random_move()).collect::<Vec<_>>();
+        for constraints in [
+            Constraints {
+                max_uses: Some(1250),
+                max_consecutive: None,
+            },
+            Constraints {
+                max_uses: None,
+                max_consecutive: Some(3),
+            },
+        ] {
+            let start = Instant::now();
+            let actual = optimize(&enemies, &constraints, &rules).unwrap().unwrap();
+            assert!(start.elapsed() < Duration::from_secs(2));
+            assert!(constraints.is_satisfied_by(&actual.moves));
+        }
+
+        let constraints = Constraints {
+            max_uses: Some(1250),
+            max_consecutive: Some(3),
+        };
+        assert!(matches!(
+            optimize(&enemies, &constraints, &rules),
+            Err(Error::TooManyStates(_))
+        ));
+        let actual = optimize(&enemies[..100], &constraints, &rules)
+            .unwrap()
+            .unwrap();
+        assert!(constraints.is_satisfied_by(&actual.moves));
+    }
+
+    #[test]
+    fn test_optimize_streak() {
+        // Winning twice in a row is worth more than playing the best valued move
+        let rules = ScoringRules::new([0, 0, 5], [1, 0, 0]).with_streak_bonus(2, 10);
+        let actual = optimize(&[Rock, Rock], &Constraints::default(), &rules)
+            .unwrap()
+            .unwrap();
+        assert_eq!(actual.moves, vec![Paper, Paper]);
+        assert_eq!(actual.score, 12);
+    }
+
+    #[test]
+    fn test_check_guide() {
+        let rounds = read_input(TEST_STR.as_bytes()).unwrap();
+        let actual =
+            check_guide(&rounds, &Constraints::default(), &ScoringRules::default()).unwrap();
+        assert_eq!(actual.score_1, 15);
+        assert_eq!(actual.score_2, 12);
+        assert_eq!(actual.optimum.unwrap().score, 24);
+    }
+}
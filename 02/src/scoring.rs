@@ -0,0 +1,130 @@
+use crate::RoundResult::{self, Draw, Lose, Win};
+use crate::RPS::{self, Paper, Rock, Scissors};
+
+/// Extra points given to every win that is at least the `length`-th consecutive win
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StreakBonus {
+    pub length: usize,
+    pub points: i32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoringRules {
+    rock: i32,
+    paper: i32,
+    scissors: i32,
+    win: i32,
+    draw: i32,
+    lose: i32,
+    streak_bonus: Option<StreakBonus>,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules::new(
+            [Rock.score(), Paper.score(), Scissors.score()],
+            [Win.score(), Draw.score(), Lose.score()],
+        )
+    }
+}
+
+impl ScoringRules {
+    /// Move values are given in `RPS::ALL` order, and outcome values in `RoundResult::ALL` order
+    pub fn new(moves: [i32; 3], outcomes: [i32; 3]) -> Self {
+        let [rock, paper, scissors] = moves;
+        let [win, draw, lose] = outcomes;
+        ScoringRules {
+            rock,
+            paper,
+            scissors,
+            win,
+            draw,
+            lose,
+            streak_bonus: None,
+        }
+    }
+
+    pub fn with_streak_bonus(mut self, length: usize, points: i32) -> Self {
+        self.streak_bonus = Some(StreakBonus { length, points });
+        self
+    }
+
+    pub fn streak_bonus(&self) -> Option<StreakBonus> {
+        self.streak_bonus
+    }
+
+    pub fn move_score(&self, yours: &RPS) -> i32 {
+        match yours {
+            Rock => self.rock,
+            Paper => self.paper,
+            Scissors => self.scissors,
+        }
+    }
+
+    pub fn outcome_score(&self, result: &RoundResult) -> i32 {
+        match result {
+            Win => self.win,
+            Draw => self.draw,
+            Lose => self.lose,
+        }
+    }
+
+    /// Score of a single round, without any streak bonus
+    pub fn round_score(&self, yours: &RPS, result: &RoundResult) -> i32 {
+        self.move_score(yours) + self.outcome_score(result)
+    }
+
+    /// Bonus of a round, given the number of consecutive wins including it
+    pub fn bonus(&self, streak: usize) -> i32 {
+        match self.streak_bonus {
+            Some(bonus) if streak > 0 && streak >= bonus.length => bonus.points,
+            _ => 0,
+        }
+    }
+
+    /// Consecutive wins after a round with the given result. Streaks stop counting at the bonus
+    /// length, as longer ones earn the same bonus, and are always 0 without a bonus.
+    pub fn next_streak(&self, streak: usize, result: &RoundResult) -> usize {
+        match self.streak_bonus {
+            Some(bonus) if *result == Win => (streak + 1).min(bonus.length.max(1)),
+            _ => 0,
+        }
+    }
+
+    /// Total score of a sequence of moves and their results
+    pub fn score<I>(&self, plays: I) -> i32
+    where
+        I: IntoIterator<Item = (RPS, RoundResult)>,
+    {
+        let mut streak = 0;
+        plays
+            .into_iter()
+            .map(|(yours, result)| {
+                streak = self.next_streak(streak, &result);
+                self.round_score(&yours, &result) + self.bonus(streak)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_bonus() {
+        let rules = ScoringRules::new([0, 0, 0], [1, 0, 0]).with_streak_bonus(2, 10);
+        let plays = [
+            (Rock, Win),
+            (Rock, Win),
+            (Rock, Win),
+            (Rock, Draw),
+            (Rock, Win),
+        ];
+        assert_eq!(rules.score(plays), 4 + 10 + 10);
+
+        assert_eq!(rules.next_streak(2, &Win), 2);
+        assert_eq!(rules.next_streak(1, &Lose), 0);
+        assert_eq!(ScoringRules::default().next_streak(0, &Win), 0);
+    }
+}
@@ -0,0 +1,364 @@
+use crate::RoundResult::{self, Win};
+use crate::{CyclicMove, Part, Round, ScoringRules, RPS};
+use std::cmp::Ordering;
+
+/// Small seeded random number generator (SplitMix64), so that tournaments are reproducible
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn random_move(&mut self) -> RPS {
+        RPS::ALL[(self.next_u64() % 3) as usize]
+    }
+}
+
+pub trait Player {
+    fn name(&self) -> String;
+
+    fn play(&mut self, rng: &mut Rng) -> RPS;
+
+    /// Called after each round, with the moves of both players
+    fn observe(&mut self, yours: RPS, enemy: RPS);
+
+    /// Called before each match, to forget about the previous enemy
+    fn reset(&mut self);
+}
+
+/// Plays the moves of a strategy guide, starting over when they are exhausted
+pub struct GuidePlayer {
+    moves: Vec<RPS>,
+    index: usize,
+}
+
+impl GuidePlayer {
+    pub fn new(rounds: &[Round], part: Part) -> Self {
+        let moves = rounds
+            .iter()
+            .map(|round| match part {
+                Part::One => round.yours(),
+                Part::Two => round.get_your_move(),
+            })
+            .collect();
+        GuidePlayer { moves, index: 0 }
+    }
+
+    pub fn from_moves(moves: Vec<RPS>) -> Self {
+        GuidePlayer { moves, index: 0 }
+    }
+}
+
+impl Player for GuidePlayer {
+    fn name(&self) -> String {
+        "guide".to_string()
+    }
+
+    fn play(&mut self, rng: &mut Rng) -> RPS {
+        if self.moves.is_empty() {
+            return rng.random_move();
+        }
+        let yours = self.moves[self.index % self.moves.len()];
+        self.index += 1;
+        yours
+    }
+
+    fn observe(&mut self, _: RPS, _: RPS) {}
+
+    fn reset(&mut self) {
+        self.index = 0;
+    }
+}
+
+pub struct RandomPlayer;
+
+impl Player for RandomPlayer {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn play(&mut self, rng: &mut Rng) -> RPS {
+        rng.random_move()
+    }
+
+    fn observe(&mut self, _: RPS, _: RPS) {}
+
+    fn reset(&mut self) {}
+}
+
+/// Beats the move the enemy played the most so far
+#[derive(Default)]
+pub struct FrequencyPlayer {
+    counts: [usize; 3],
+}
+
+impl Player for FrequencyPlayer {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn play(&mut self, rng: &mut Rng) -> RPS {
+        match predict(&self.counts) {
+            Some(predicted) => RPS::for_goal(&predicted, &Win),
+            None => rng.random_move(),
+        }
+    }
+
+    fn observe(&mut self, _: RPS, enemy: RPS) {
+        self.counts[enemy.index()] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+}
+
+/// Beats the move the enemy played the most after its last move
+#[derive(Default)]
+pub struct MarkovPlayer {
+    transitions: [[usize; 3]; 3],
+    last: Option<RPS>,
+}
+
+impl Player for MarkovPlayer {
+    fn name(&self) -> String {
+        "markov".to_string()
+    }
+
+    fn play(&mut self, rng: &mut Rng) -> RPS {
+        let predicted = self
+            .last
+            .and_then(|last| predict(&self.transitions[last.index()]));
+        match predicted {
+            Some(predicted) => RPS::for_goal(&predicted, &Win),
+            None => rng.random_move(),
+        }
+    }
+
+    fn observe(&mut self, _: RPS, enemy: RPS) {
+        if let Some(last) = self.last {
+            self.transitions[last.index()][enemy.index()] += 1;
+        }
+        self.last = Some(enemy);
+    }
+
+    fn reset(&mut self) {
+        self.transitions = [[0; 3]; 3];
+        self.last = None;
+    }
+}
+
+/// Keeps its move after a win, otherwise plays what would have beaten the enemy's last move
+#[derive(Default)]
+pub struct WinStayLoseShiftPlayer {
+    last: Option<(RPS, RPS)>,
+}
+
+impl Player for WinStayLoseShiftPlayer {
+    fn name(&self) -> String {
+        "win-stay-lose-shift".to_string()
+    }
+
+    fn play(&mut self, rng: &mut Rng) -> RPS {
+        match self.last {
+            Some((yours, enemy)) if yours.against(&enemy) == Win => yours,
+            Some((_, enemy)) => RPS::for_goal(&enemy, &Win),
+            None => rng.random_move(),
+        }
+    }
+
+    fn observe(&mut self, yours: RPS, enemy: RPS) {
+        self.last = Some((yours, enemy));
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+// Most frequent move, if there is a single one
+fn predict(counts: &[usize; 3]) -> Option<RPS> {
+    let max = counts.iter().copied().max()?;
+    let mut best = RPS::ALL.into_iter().filter(|m| counts[m.index()] == max);
+    match (best.next(), best.next()) {
+        (Some(predicted), None) if max > 0 => Some(predicted),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub score: i64,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// Round-robin tournament: every player plays a match of `rounds` rounds against every other one
+pub struct Tournament {
+    rounds: usize,
+    seed: u64,
+    rules: ScoringRules,
+}
+
+impl Tournament {
+    pub fn new(rounds: usize, seed: u64) -> Self {
+        Tournament {
+            rounds,
+            seed,
+            rules: ScoringRules::default(),
+        }
+    }
+
+    pub fn with_rules(mut self, rules: ScoringRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Standings sorted by score, best first
+    pub fn run(&self, players: &mut [Box<dyn Player>]) -> Vec<Standing> {
+        let mut rng = Rng::new(self.seed);
+        let mut standings = players
+            .iter()
+            .map(|player| Standing {
+                name: player.name(),
+                score: 0,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for first in 0..players.len() {
+            for second in first + 1..players.len() {
+                let (left, right) = players.split_at_mut(second);
+                let (first_score, second_score) =
+                    self.play_match(left[first].as_mut(), right[0].as_mut(), &mut rng);
+
+                standings[first].score += first_score;
+                standings[second].score += second_score;
+                match first_score.cmp(&second_score) {
+                    Ordering::Greater => {
+                        standings[first].wins += 1;
+                        standings[second].losses += 1;
+                    }
+                    Ordering::Equal => {
+                        standings[first].draws += 1;
+                        standings[second].draws += 1;
+                    }
+                    Ordering::Less => {
+                        standings[first].losses += 1;
+                        standings[second].wins += 1;
+                    }
+                }
+            }
+        }
+
+        standings.sort_by(|a, b| b.score.cmp(&a.score).then(a.name.cmp(&b.name)));
+        standings
+    }
+
+    fn play_match(
+        &self,
+        first: &mut dyn Player,
+        second: &mut dyn Player,
+        rng: &mut Rng,
+    ) -> (i64, i64) {
+        first.reset();
+        second.reset();
+
+        let mut first_score = MatchScore::default();
+        let mut second_score = MatchScore::default();
+        for _ in 0..self.rounds {
+            let first_move = first.play(rng);
+            let second_move = second.play(rng);
+            first.observe(first_move, second_move);
+            second.observe(second_move, first_move);
+
+            first_score.add(&self.rules, first_move, first_move.against(&second_move));
+            second_score.add(&self.rules, second_move, second_move.against(&first_move));
+        }
+        (first_score.total, second_score.total)
+    }
+}
+
+#[derive(Default)]
+struct MatchScore {
+    total: i64,
+    streak: usize,
+}
+
+impl MatchScore {
+    fn add(&mut self, rules: &ScoringRules, yours: RPS, result: RoundResult) {
+        self.streak = rules.next_streak(self.streak, &result);
+        self.total += (rules.round_score(&yours, &result) + rules.bonus(self.streak)) as i64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RPS::{Paper, Rock};
+
+    fn players() -> Vec<Box<dyn Player>> {
+        vec![
+            Box::new(GuidePlayer::from_moves(vec![Rock])),
+            Box::new(RandomPlayer),
+            Box::new(FrequencyPlayer::default()),
+            Box::new(MarkovPlayer::default()),
+            Box::new(WinStayLoseShiftPlayer::default()),
+        ]
+    }
+
+    #[test]
+    fn test_match() {
+        let mut players: Vec<Box<dyn Player>> = vec![
+            Box::new(GuidePlayer::from_moves(vec![Paper])),
+            Box::new(GuidePlayer::from_moves(vec![Rock])),
+        ];
+        let actual = Tournament::new(10, 0).run(&mut players);
+        assert_eq!(actual[0].score, 80);
+        assert_eq!(actual[0].wins, 1);
+        assert_eq!(actual[1].score, 10);
+        assert_eq!(actual[1].losses, 1);
+    }
+
+    #[test]
+    fn test_tournament() {
+        let tournament = Tournament::new(100, 42);
+        let actual = tournament.run(&mut players());
+        assert_eq!(actual, tournament.run(&mut players()));
+        assert_eq!(actual.len(), 5);
+        assert!(actual.iter().all(|s| s.wins + s.draws + s.losses == 4));
+
+        // Always playing rock is the easiest to predict
+        assert_eq!(actual.last().unwrap().name, "guide");
+    }
+
+    #[test]
+    fn test_predictors() {
+        let mut rng = Rng::new(0);
+        let mut player = FrequencyPlayer::default();
+        player.observe(Paper, Rock);
+        assert_eq!(player.play(&mut rng), Paper);
+
+        let mut player = MarkovPlayer::default();
+        player.observe(Rock, Rock);
+        player.observe(Rock, Paper);
+        player.observe(Rock, Rock);
+        assert_eq!(player.play(&mut rng), RPS::Scissors);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::RoundResult;
+use crate::{Error, Round, ScoringRules, RPS};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Which interpretation of the second column to use
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(Error::Parse(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraceFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for TraceFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(TraceFormat::Csv),
+            "json" => Ok(TraceFormat::Json),
+            _ => Err(Error::Parse(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundTrace {
+    pub enemy: RPS,
+    pub yours: RPS,
+    pub result: RoundResult,
+    pub move_points: i32,
+    pub outcome_points: i32,
+    pub bonus_points: i32,
+    pub total: i32,
+}
+
+pub fn trace(rounds: &[Round], part: Part, rules: &ScoringRules) -> Vec<RoundTrace> {
+    let mut streak = 0;
+    let mut total = 0;
+    rounds
+        .iter()
+        .map(|round| {
+            let (yours, result) = match part {
+                Part::One => (round.yours(), round.get_round_result()),
+                Part::Two => (round.get_your_move(), round.goal()),
+            };
+            streak = rules.next_streak(streak, &result);
+
+            let move_points = rules.move_score(&yours);
+            let outcome_points = rules.outcome_score(&result);
+            let bonus_points = rules.bonus(streak);
+            total += move_points + outcome_points + bonus_points;
+
+            RoundTrace {
+                enemy: round.enemy(),
+                yours,
+                result,
+                move_points,
+                outcome_points,
+                bonus_points,
+                total,
+            }
+        })
+        .collect()
+}
+
+pub fn export_trace(traces: &[RoundTrace], format: TraceFormat) -> String {
+    match format {
+        TraceFormat::Csv => to_csv(traces),
+        TraceFormat::Json => to_json(traces),
+    }
+}
+
+fn to_csv(traces: &[RoundTrace]) -> String {
+    let mut output =
+        String::from("enemy,yours,result,move_points,outcome_points,bonus_points,total\n");
+    for t in traces {
+        // Writing to a String cannot fail
+        let _ = writeln!(
+            output,
+            "{},{},{},{},{},{},{}",
+            t.enemy, t.yours, t.result, t.move_points, t.outcome_points, t.bonus_points, t.total
+        );
+    }
+    output
+}
+
+fn to_json(traces: &[RoundTrace]) -> String {
+    let entries = traces
+        .iter()
+        .map(|t| {
+            format!(
+                r#"{{"enemy":"{}","yours":"{}","result":"{}","move_points":{},"outcome_points":{},"bonus_points":{},"total":{}}}"#,
+                t.enemy, t.yours, t.result, t.move_points, t.outcome_points, t.bonus_points, t.total
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", entries.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_score_2, read_input, TEST_STR};
+
+    #[test]
+    fn test_trace() {
+        let rounds = read_input(TEST_STR.as_bytes()).unwrap();
+        let rules = ScoringRules::default();
+
+        let actual = trace(&rounds, Part::One, &rules);
+        let expected = "enemy,yours,result,move_points,outcome_points,bonus_points,total
+rock,paper,win,2,6,0,8
+paper,rock,lose,1,0,0,9
+scissors,scissors,draw,3,3,0,15
+";
+        assert_eq!(export_trace(&actual, TraceFormat::Csv), expected);
+
+        let actual = trace(&rounds, Part::Two, &rules);
+        assert_eq!(actual.last().unwrap().total, compute_score_2(&rounds));
+        let expected = r#"[{"enemy":"rock","yours":"rock","result":"draw","move_points":1,"outcome_points":3,"bonus_points":0,"total":4}"#;
+        assert!(export_trace(&actual, TraceFormat::Json).starts_with(expected));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    complete_groups, find_group_common_items, Compartments, Error, Grouping, PriorityTable,
+};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ItemFrequency {
+    /// Number of items in the left compartments
+    pub left: usize,
+    /// Number of items in the right compartments
+    pub right: usize,
+    /// Number of rucksacks with the item in both compartments
+    pub duplicated: usize,
+    /// Number of groups with the item in every rucksack
+    pub badges: usize,
+}
+
+impl ItemFrequency {
+    pub fn total(&self) -> usize {
+        self.left + self.right
+    }
+}
+
+/// Frequencies of every item type of a priority table, sorted by priority
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ItemReport {
+    pub items: Vec<(char, ItemFrequency)>,
+}
+
+impl ItemReport {
+    pub fn frequency(&self, item: char) -> Option<&ItemFrequency> {
+        self.items
+            .iter()
+            .find(|(other, _)| *other == item)
+            .map(|(_, frequency)| frequency)
+    }
+
+    /// Item types most often found in both compartments, most duplicated first
+    pub fn most_duplicated(&self, count: usize) -> Vec<(char, usize)> {
+        let mut duplicated = self
+            .items
+            .iter()
+            .filter(|(_, frequency)| frequency.duplicated > 0)
+            .map(|(item, frequency)| (*item, frequency.duplicated))
+            .collect::<Vec<_>>();
+        duplicated.sort_by_key(|(_, duplicated)| std::cmp::Reverse(*duplicated));
+        duplicated.truncate(count);
+        duplicated
+    }
+
+    /// Item types found in no rucksack
+    pub fn missing(&self) -> Vec<char> {
+        self.items
+            .iter()
+            .filter(|(_, frequency)| frequency.total() == 0)
+            .map(|(item, _)| *item)
+            .collect()
+    }
+}
+
+impl Display for ItemReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "item left right total duplicated badges")?;
+        for (item, frequency) in self.items.iter().filter(|(_, f)| f.total() > 0) {
+            writeln!(
+                f,
+                "{} {} {} {} {} {}",
+                item,
+                frequency.left,
+                frequency.right,
+                frequency.total(),
+                frequency.duplicated,
+                frequency.badges
+            )?;
+        }
+
+        let duplicated = self
+            .most_duplicated(5)
+            .iter()
+            .map(|(item, count)| format!("{item}={count}"))
+            .collect::<Vec<_>>();
+        writeln!(f, "most duplicated: {}", duplicated.join(" "))?;
+        writeln!(
+            f,
+            "missing: {}",
+            self.missing().into_iter().collect::<String>()
+        )
+    }
+}
+
+pub fn analyze<R>(
+    rucksacks: &[R],
+    table: &PriorityTable,
+    grouping: &Grouping,
+) -> Result<ItemReport, Error>
+where
+    R: Compartments,
+{
+    let mut items = table
+        .items()
+        .map(|item| (item, ItemFrequency::default()))
+        .collect::<Vec<_>>();
+
+    for rucksack in rucksacks {
+        for item in rucksack.left().chars() {
+            frequency(&mut items, table, item)?.left += 1;
+        }
+        for item in rucksack.right().chars() {
+            frequency(&mut items, table, item)?.right += 1;
+        }
+        for item in rucksack.common_items(table)?.iter(table) {
+            frequency(&mut items, table, item)?.duplicated += 1;
+        }
+    }
+
+    for group in complete_groups(rucksacks, grouping)?.chunks(grouping.size) {
+        for item in find_group_common_items(group, table)?.iter(table) {
+            frequency(&mut items, table, item)?.badges += 1;
+        }
+    }
+    Ok(ItemReport { items })
+}
+
+fn frequency<'a>(
+    items: &'a mut [(char, ItemFrequency)],
+    table: &PriorityTable,
+    item: char,
+) -> Result<&'a mut ItemFrequency, Error> {
+    let slot = table.slot(item).ok_or(Error::Invalid(item))?;
+    Ok(&mut items[slot].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, TEST_STR};
+
+    #[test]
+    fn test_analyze() {
+        let rucksacks = read_input(TEST_STR.as_bytes()).unwrap();
+        let table = PriorityTable::default();
+        let actual = analyze(&rucksacks, &table, &Grouping::default()).unwrap();
+
+        let expected = ItemFrequency {
+            left: 4,
+            right: 1,
+            duplicated: 1,
+            badges: 0,
+        };
+        assert_eq!(actual.frequency('P'), Some(&expected));
+        assert_eq!(actual.frequency('r').unwrap().badges, 1);
+        assert_eq!(actual.frequency('Z').unwrap().badges, 1);
+
+        let duplicated = actual.most_duplicated(10);
+        assert_eq!(duplicated.len(), 6);
+        assert!(duplicated.iter().all(|(_, count)| *count == 1));
+        assert_eq!(
+            actual.missing().into_iter().collect::<String>(),
+            "aeiklouxyAEIKOUXY"
+        );
+    }
+}
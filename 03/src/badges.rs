@@ -0,0 +1,219 @@
+use crate::{get_priority, Compartments, Error, ItemSet, PriorityTable};
+use std::collections::HashMap;
+
+// Used rucksacks are tracked in a u64 bitmask
+const MAX_RUCKSACKS: usize = 64;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Objective {
+    Minimize,
+    Maximize,
+}
+
+/// Groups of rucksacks, given by their index, with the badge shared by each group
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeAssignment {
+    pub groups: Vec<Vec<usize>>,
+    pub badges: Vec<char>,
+    pub priority: i32,
+}
+
+#[derive(Clone, Copy)]
+struct Choice {
+    total: i32,
+    group: u64,
+    badge: char,
+}
+
+struct Solver<'a> {
+    table: &'a PriorityTable,
+    sets: Vec<ItemSet>,
+    size: usize,
+    objective: Objective,
+    memo: HashMap<u64, Option<Choice>>,
+}
+
+/// Splits unordered rucksacks into groups of `group_size`, each group sharing exactly one item.
+///
+/// Returns `None` if there is no such partition. The search is exponential, but
+/// memoized on the set of rucksacks already grouped. Only up to 64 rucksacks are
+/// supported, larger inputs such as a full puzzle input fail with `Error::TooManyRucksacks`.
+pub fn assign_badges<R>(
+    rucksacks: &[R],
+    table: &PriorityTable,
+    group_size: usize,
+    objective: Objective,
+) -> Result<Option<BadgeAssignment>, Error>
+where
+    R: Compartments,
+{
+    if group_size == 0 {
+        return Err(Error::GroupSize(group_size));
+    }
+    if rucksacks.len() > MAX_RUCKSACKS {
+        return Err(Error::TooManyRucksacks(rucksacks.len()));
+    }
+    if !rucksacks.len().is_multiple_of(group_size) {
+        return Ok(None);
+    }
+
+    let sets = rucksacks
+        .iter()
+        .map(|r| r.items(table))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut solver = Solver {
+        table,
+        sets,
+        size: group_size,
+        objective,
+        memo: HashMap::new(),
+    };
+
+    let priority = match solver.solve(0) {
+        Some(priority) => priority,
+        None => return Ok(None),
+    };
+
+    let mut groups = Vec::new();
+    let mut badges = Vec::new();
+    let mut used = 0;
+    while let Some(Some(choice)) = solver.memo.get(&used) {
+        groups.push(
+            (0..rucksacks.len())
+                .filter(|i| choice.group & (1 << i) != 0)
+                .collect(),
+        );
+        badges.push(choice.badge);
+        used |= choice.group;
+    }
+
+    Ok(Some(BadgeAssignment {
+        groups,
+        badges,
+        priority,
+    }))
+}
+
+impl Solver<'_> {
+    fn solve(&mut self, used: u64) -> Option<i32> {
+        if used.count_ones() as usize == self.sets.len() {
+            return Some(0);
+        }
+        if let Some(choice) = self.memo.get(&used) {
+            return choice.map(|choice| choice.total);
+        }
+
+        // The first rucksack not yet used has to be in some group
+        let first = (!used).trailing_zeros() as usize;
+        let mut groups = Vec::new();
+        self.find_groups(first + 1, 1 << first, self.sets[first], used, &mut groups);
+
+        let mut best: Option<Choice> = None;
+        for (group, badge, priority) in groups {
+            if let Some(rest) = self.solve(used | group) {
+                let total = rest + priority;
+                let better = match (best, self.objective) {
+                    (None, _) => true,
+                    (Some(best), Objective::Minimize) => total < best.total,
+                    (Some(best), Objective::Maximize) => total > best.total,
+                };
+                if better {
+                    best = Some(Choice {
+                        total,
+                        group,
+                        badge,
+                    });
+                }
+            }
+        }
+
+        self.memo.insert(used, best);
+        best.map(|choice| choice.total)
+    }
+
+    fn find_groups(
+        &self,
+        start: usize,
+        group: u64,
+        common: ItemSet,
+        used: u64,
+        groups: &mut Vec<(u64, char, i32)>,
+    ) {
+        if group.count_ones() as usize == self.size {
+            if let (1, Some(badge)) = (common.len(), common.first(self.table)) {
+                if let Ok(priority) = get_priority(badge, self.table) {
+                    groups.push((group, badge, priority));
+                }
+            }
+            return;
+        }
+
+        for next in start..self.sets.len() {
+            if used & (1 << next) == 0 {
+                let common = common.intersection(&self.sets[next]);
+                if !common.is_empty() {
+                    self.find_groups(next + 1, group | (1 << next), common, used, groups);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_group_common_items, read_input, TEST_STR};
+
+    #[test]
+    fn test_assign_badges() {
+        let table = PriorityTable::default();
+        // The puzzle groups, interleaved
+        let lines = TEST_STR.lines().collect::<Vec<_>>();
+        let input = [0, 3, 1, 4, 2, 5].map(|i| lines[i]).join("\n");
+        let rucksacks = read_input(input.as_bytes()).unwrap();
+        let min = assign_badges(&rucksacks, &table, 3, Objective::Minimize)
+            .unwrap()
+            .unwrap();
+        let max = assign_badges(&rucksacks, &table, 3, Objective::Maximize)
+            .unwrap()
+            .unwrap();
+        assert_eq!(min.priority, 53);
+        assert_eq!(max.priority, 70);
+
+        for assignment in [min, max] {
+            assert_eq!(assignment.groups.len(), 2);
+            for (group, badge) in assignment.groups.iter().zip(&assignment.badges) {
+                let group = group
+                    .iter()
+                    .map(|i| rucksacks[*i].clone())
+                    .collect::<Vec<_>>();
+                let common = find_group_common_items(&group, &table).unwrap();
+                assert_eq!(common.iter(&table).collect::<Vec<_>>(), vec![*badge]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_assignment() {
+        let table = PriorityTable::default();
+        let rucksacks = read_input("aa\nbb\n".as_bytes()).unwrap();
+        assert_eq!(
+            assign_badges(&rucksacks, &table, 2, Objective::Minimize).unwrap(),
+            None
+        );
+        assert_eq!(
+            assign_badges(&rucksacks, &table, 3, Objective::Minimize).unwrap(),
+            None
+        );
+        assert!(matches!(
+            assign_badges(&rucksacks, &table, 0, Objective::Minimize),
+            Err(Error::GroupSize(0))
+        ));
+
+        let rucksacks = read_input("aa\n".repeat(66).as_bytes()).unwrap();
+        assert!(matches!(
+            assign_badges(&rucksacks, &table, 3, Objective::Minimize),
+            Err(Error::TooManyRucksacks(66))
+        ));
+    }
+}
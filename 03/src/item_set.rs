@@ -0,0 +1,90 @@
+use crate::{Error, PriorityTable};
+
+/// A set of item types, stored as a bitmask indexed by the position of each item in a priority table
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new() -> Self {
+        ItemSet(0)
+    }
+
+    pub fn from_items(items: &str, table: &PriorityTable) -> Result<Self, Error> {
+        let mut set = ItemSet::new();
+        for item in items.chars() {
+            set.insert(item, table)?;
+        }
+        Ok(set)
+    }
+
+    pub fn insert(&mut self, item: char, table: &PriorityTable) -> Result<(), Error> {
+        let slot = table.slot(item).ok_or(Error::Invalid(item))?;
+        self.0 |= 1 << slot;
+        Ok(())
+    }
+
+    pub fn contains(&self, item: char, table: &PriorityTable) -> bool {
+        match table.slot(item) {
+            Some(slot) => self.0 & (1 << slot) != 0,
+            None => false,
+        }
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Item with the lowest priority
+    pub fn first(&self, table: &PriorityTable) -> Option<char> {
+        self.iter(table).next()
+    }
+
+    /// Items, sorted by priority
+    pub fn iter<'a>(&self, table: &'a PriorityTable) -> impl Iterator<Item = char> + 'a {
+        let bits = self.0;
+        (0..table.len())
+            .filter(move |slot| bits & (1 << slot) != 0)
+            .filter_map(|slot| table.item(slot))
+    }
+
+    pub fn to_string(&self, table: &PriorityTable) -> String {
+        self.iter(table).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_set() {
+        let table = PriorityTable::default();
+        let left = ItemSet::from_items("vJrwpWtwJgWr", &table).unwrap();
+        let right = ItemSet::from_items("hcsFMMfFFhFp", &table).unwrap();
+        let common = left.intersection(&right);
+        assert_eq!(common.iter(&table).collect::<Vec<_>>(), vec!['p']);
+        assert!(left.contains('J', &table));
+        assert!(!left.contains('h', &table));
+
+        let all = ItemSet::from_items("zZaA", &table).unwrap();
+        assert_eq!(all.to_string(&table), "azAZ");
+        assert_eq!(all.union(&common).len(), 5);
+
+        assert!(matches!(
+            ItemSet::from_items("a1", &table),
+            Err(Error::Invalid('1'))
+        ));
+    }
+}
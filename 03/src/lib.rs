@@ -1,56 +1,196 @@
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use std::io::{BufRead, BufReader, Error as IoError, Read};
 use thiserror::Error;
 
+mod analytics;
+mod badges;
+mod item_set;
+mod priority;
+mod reorganize;
+
+pub use analytics::{analyze, ItemFrequency, ItemReport};
+pub use badges::{assign_badges, BadgeAssignment, Objective};
+pub use item_set::ItemSet;
+pub use priority::PriorityTable;
+pub use reorganize::{reorganize, Compartment, Move, Reorganization};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("I/O error")]
     IO(#[from] IoError),
-    #[error("Invalid input {}", .0)]
-    Parse(String),
-    #[error("No common item found")]
-    NoCommon,
+    #[error("Odd number of items {} on line {}", .1, .0)]
+    OddLength(usize, usize),
+    #[error("Invalid item {} at position {} on line {}", .2, .1, .0)]
+    InvalidItem(usize, usize, char),
+    #[error("No common item found in {} on lines {}-{}", .2, .0, .1)]
+    NoCommon(usize, usize, String),
+    #[error("Several common items {} found in {} on lines {}-{}", .2, .3, .0, .1)]
+    SeveralCommon(usize, usize, String, String),
     #[error("Invalid item {}", .0)]
-    Invalid(u8),
+    Invalid(char),
+    #[error("Invalid group size {}", .0)]
+    GroupSize(usize),
+    #[error("Incomplete group on lines {}-{}", .0, .1)]
+    IncompleteGroup(usize, usize),
+    #[error("Too many rucksacks {}", .0)]
+    TooManyRucksacks(usize),
+    #[error("Invalid priority table entry {}", .0)]
+    PriorityTable(String),
+    #[error("Too many item types {}", .0)]
+    TooManyItems(usize),
+}
+
+/// What to do with the last group, if there are not enough rucksacks to fill it
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum IncompletePolicy {
+    #[default]
+    Fail,
+    Skip,
+    Process,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Grouping {
+    pub size: usize,
+    pub incomplete: IncompletePolicy,
+    /// Fail if a group does not have exactly one common item
+    pub strict: bool,
+}
+
+impl Default for Grouping {
+    fn default() -> Self {
+        Grouping {
+            size: 3,
+            incomplete: IncompletePolicy::Fail,
+            strict: false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Rucksack {
-    left: Vec<u8>,
-    right: Vec<u8>,
+    line: usize,
+    left: String,
+    right: String,
 }
 
 impl Rucksack {
-    fn new(left: Vec<u8>, right: Vec<u8>) -> Self {
-        Rucksack { left, right }
+    fn new(line: usize, left: String, right: String) -> Self {
+        Rucksack { line, left, right }
     }
+}
+
+/// A rucksack borrowing its items from the input
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RucksackRef<'a> {
+    line: usize,
+    left: &'a str,
+    right: &'a str,
+}
 
-    fn find_common(&self) -> Option<u8> {
-        let left = self.left.iter().copied().collect::<HashSet<_>>();
-        let right = self.right.iter().copied().collect::<HashSet<_>>();
-        left.intersection(&right).next().copied()
+impl From<RucksackRef<'_>> for Rucksack {
+    fn from(rucksack: RucksackRef<'_>) -> Self {
+        Rucksack::new(
+            rucksack.line,
+            rucksack.left.to_string(),
+            rucksack.right.to_string(),
+        )
     }
 }
 
-fn find_common<I, J>(mut iter: I) -> Option<u8>
-where
-    I: Iterator<Item = J>,
-    J: Iterator<Item = u8>,
-{
-    // Initialize
-    let mut common = iter.next()?.collect::<HashSet<_>>();
+/// Items of owned or borrowed rucksacks
+pub trait Compartments: Display {
+    /// Line of the input the rucksack was read from, numbered from 1
+    fn line(&self) -> usize;
 
-    // Intersect existing with the next ones
-    for item in iter {
-        let next = item.collect::<HashSet<_>>();
-        common = common.intersection(&next).copied().collect::<HashSet<_>>();
+    fn left(&self) -> &str;
+
+    fn right(&self) -> &str;
+
+    /// Items found in both compartments
+    fn common_items(&self, table: &PriorityTable) -> Result<ItemSet, Error> {
+        let left = ItemSet::from_items(self.left(), table)?;
+        let right = ItemSet::from_items(self.right(), table)?;
+        Ok(left.intersection(&right))
     }
 
-    common.into_iter().next()
+    fn items(&self, table: &PriorityTable) -> Result<ItemSet, Error> {
+        let left = ItemSet::from_items(self.left(), table)?;
+        let right = ItemSet::from_items(self.right(), table)?;
+        Ok(left.union(&right))
+    }
+}
+
+impl Compartments for Rucksack {
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn left(&self) -> &str {
+        &self.left
+    }
+
+    fn right(&self) -> &str {
+        &self.right
+    }
+}
+
+impl Compartments for RucksackRef<'_> {
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn left(&self) -> &str {
+        self.left
+    }
+
+    fn right(&self) -> &str {
+        self.right
+    }
+}
+
+impl Display for Rucksack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.left, self.right)
+    }
+}
+
+impl Display for RucksackRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.left, self.right)
+    }
+}
+
+/// Items found in every rucksack of the group
+pub fn find_group_common_items<R>(group: &[R], table: &PriorityTable) -> Result<ItemSet, Error>
+where
+    R: Compartments,
+{
+    let sets = group
+        .iter()
+        .map(|r| r.items(table))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(find_common(sets.into_iter()).unwrap_or_default())
+}
+
+fn find_common<I>(mut iter: I) -> Option<ItemSet>
+where
+    I: Iterator<Item = ItemSet>,
+{
+    let first = iter.next()?;
+    Some(iter.fold(first, |common, next| common.intersection(&next)))
 }
 
 pub fn read_input<R>(read: R) -> Result<Vec<Rucksack>, Error>
+where
+    R: Read,
+{
+    read_input_with(read, &PriorityTable::default())
+}
+
+/// Like `read_input`, with the item types of a custom priority table
+pub fn read_input_with<R>(read: R, table: &PriorityTable) -> Result<Vec<Rucksack>, Error>
 where
     R: Read,
 {
@@ -60,66 +200,255 @@ where
         .collect::<Result<Vec<_>, _>>()?;
     let rucksacks = lines
         .iter()
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(read_line)
+        .enumerate()
+        .map(|(i, s)| (i + 1, s.trim()))
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(line, s)| read_line(line, s, table))
         .collect::<Result<Vec<_>, _>>()?;
     Ok(rucksacks)
 }
 
-fn get_priority(c: u8) -> Result<i32, Error> {
-    match c {
-        97..=122 => Ok((c - 96) as i32),
-        65..=90 => Ok((c - 65 + 27) as i32),
-        _ => Err(Error::Invalid(c)),
+/// Like `read_input_with`, but the rucksacks borrow their items from `input` instead of copying them
+pub fn parse_rucksacks<'a>(
+    input: &'a str,
+    table: &'a PriorityTable,
+) -> impl Iterator<Item = Result<RucksackRef<'a>, Error>> + 'a {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, s)| (i + 1, s.trim()))
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(line, s)| split_line(line, s, table))
+}
+
+/// Valid rucksacks, and the issues found on the other lines
+#[derive(Debug, Default)]
+pub struct LenientInput {
+    pub rucksacks: Vec<Rucksack>,
+    pub issues: Vec<Error>,
+}
+
+/// Like `read_input`, but invalid lines are reported instead of failing. Blank lines are
+/// skipped, and are not reported.
+pub fn read_input_lenient<R>(read: R) -> Result<LenientInput, Error>
+where
+    R: Read,
+{
+    read_input_lenient_with(read, &PriorityTable::default())
+}
+
+pub fn read_input_lenient_with<R>(read: R, table: &PriorityTable) -> Result<LenientInput, Error>
+where
+    R: Read,
+{
+    let mut input = LenientInput::default();
+    for (i, line) in BufReader::new(read).lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            match read_line(i + 1, line, table) {
+                Ok(rucksack) => input.rucksacks.push(rucksack),
+                Err(issue) => input.issues.push(issue),
+            }
+        }
     }
+    Ok(input)
 }
 
-fn read_line(input: &str) -> Result<Rucksack, Error> {
-    let bytes = input.as_bytes();
-    let half = bytes.len() / 2;
-    let left = &bytes[0..half];
-    let right = &bytes[half..];
+fn get_priority(item: char, table: &PriorityTable) -> Result<i32, Error> {
+    table.priority(item).ok_or(Error::Invalid(item))
+}
 
-    if left.len() == right.len() {
-        Ok(Rucksack::new(left.to_vec(), right.to_vec()))
-    } else {
-        Err(Error::Parse(input.to_string()))
+fn read_line(line: usize, input: &str, table: &PriorityTable) -> Result<Rucksack, Error> {
+    split_line(line, input, table).map(Rucksack::from)
+}
+
+// Lines and positions are numbered from 1
+fn split_line<'a>(
+    line: usize,
+    input: &'a str,
+    table: &PriorityTable,
+) -> Result<RucksackRef<'a>, Error> {
+    if let Some((i, c)) = input
+        .chars()
+        .enumerate()
+        .find(|(_, c)| table.priority(*c).is_none())
+    {
+        return Err(Error::InvalidItem(line, i + 1, c));
+    }
+    let len = input.chars().count();
+    if !len.is_multiple_of(2) {
+        return Err(Error::OddLength(line, len));
     }
+
+    let middle = input
+        .char_indices()
+        .nth(len / 2)
+        .map_or(input.len(), |(i, _)| i);
+    let (left, right) = input.split_at(middle);
+    Ok(RucksackRef { line, left, right })
 }
 
-pub fn compute_priorities(rucksacks: Vec<Rucksack>) -> Result<i32, Error> {
-    rucksacks.into_iter().map(find_rucksack_priority).sum()
+pub fn compute_priorities<R>(rucksacks: Vec<R>) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    compute_priorities_with(rucksacks, &PriorityTable::default())
 }
 
-fn find_rucksack_priority(r: Rucksack) -> Result<i32, Error> {
-    let common = r.find_common().ok_or(Error::NoCommon)?;
-    get_priority(common)
+/// Like `compute_priorities`, with the priorities of a custom table
+pub fn compute_priorities_with<R>(rucksacks: Vec<R>, table: &PriorityTable) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    rucksacks
+        .iter()
+        .map(|r| find_rucksack_priority(r, table, false))
+        .sum()
 }
 
-pub fn compute_chunked_priorities(rucksacks: Vec<Rucksack>) -> Result<i32, Error> {
+/// Like `compute_priorities`, but fails if a rucksack does not have exactly one common item
+pub fn compute_priorities_strict<R>(rucksacks: Vec<R>) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    compute_priorities_strict_with(rucksacks, &PriorityTable::default())
+}
+
+pub fn compute_priorities_strict_with<R>(
+    rucksacks: Vec<R>,
+    table: &PriorityTable,
+) -> Result<i32, Error>
+where
+    R: Compartments,
+{
     rucksacks
-        .into_iter()
-        .chunks(3)
-        .into_iter()
-        .map(find_chunk_priority)
+        .iter()
+        .map(|r| find_rucksack_priority(r, table, true))
         .sum()
 }
 
-fn find_chunk_priority<I>(chunk: I) -> Result<i32, Error>
+fn find_rucksack_priority<R>(r: &R, table: &PriorityTable, strict: bool) -> Result<i32, Error>
 where
-    I: Iterator<Item = Rucksack>,
+    R: Compartments,
 {
-    let iter = chunk.map(|r| r.left.into_iter().chain(r.right.into_iter()));
-    let common = find_common(iter).ok_or(Error::NoCommon)?;
-    get_priority(common)
+    let lines = (r.line(), r.line());
+    let common = single_item(r.common_items(table)?, table, strict, lines, || {
+        r.to_string()
+    })?;
+    get_priority(common, table)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn compute_chunked_priorities<R>(rucksacks: Vec<R>) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    compute_grouped_priorities(rucksacks, &Grouping::default())
+}
+
+/// Like `compute_chunked_priorities`, with the priorities of a custom table
+pub fn compute_chunked_priorities_with<R>(
+    rucksacks: Vec<R>,
+    table: &PriorityTable,
+) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    compute_grouped_priorities_with(rucksacks, &Grouping::default(), table)
+}
+
+/// Like `compute_chunked_priorities`, but fails if a group does not have exactly one common item
+pub fn compute_chunked_priorities_strict<R>(rucksacks: Vec<R>) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    let grouping = Grouping {
+        strict: true,
+        ..Grouping::default()
+    };
+    compute_grouped_priorities(rucksacks, &grouping)
+}
 
-    const TEST_STR: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
+pub fn compute_grouped_priorities<R>(rucksacks: Vec<R>, grouping: &Grouping) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    compute_grouped_priorities_with(rucksacks, grouping, &PriorityTable::default())
+}
+
+pub fn compute_grouped_priorities_with<R>(
+    rucksacks: Vec<R>,
+    grouping: &Grouping,
+    table: &PriorityTable,
+) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    complete_groups(&rucksacks, grouping)?
+        .chunks(grouping.size)
+        .map(|chunk| find_chunk_priority(chunk, table, grouping.strict))
+        .sum()
+}
+
+// Rucksacks to split in groups, according to the incomplete group policy
+fn complete_groups<'a, R>(rucksacks: &'a [R], grouping: &Grouping) -> Result<&'a [R], Error>
+where
+    R: Compartments,
+{
+    if grouping.size == 0 {
+        return Err(Error::GroupSize(grouping.size));
+    }
+
+    let remaining = rucksacks.len() % grouping.size;
+    let complete = rucksacks.len() - remaining;
+    match grouping.incomplete {
+        IncompletePolicy::Fail if remaining > 0 => Err(Error::IncompleteGroup(
+            rucksacks[complete].line(),
+            rucksacks[rucksacks.len() - 1].line(),
+        )),
+        IncompletePolicy::Skip => Ok(&rucksacks[..complete]),
+        _ => Ok(rucksacks),
+    }
+}
+
+fn find_chunk_priority<R>(chunk: &[R], table: &PriorityTable, strict: bool) -> Result<i32, Error>
+where
+    R: Compartments,
+{
+    let common = find_group_common_items(chunk, table)?;
+    let lines = (
+        chunk.first().map_or(0, |r| r.line()),
+        chunk.last().map_or(0, |r| r.line()),
+    );
+    let common = single_item(common, table, strict, lines, || chunk.iter().join(", "))?;
+    get_priority(common, table)
+}
+
+// When not strict, we pick the item with the lowest priority
+fn single_item<F>(
+    common: ItemSet,
+    table: &PriorityTable,
+    strict: bool,
+    (first, last): (usize, usize),
+    name: F,
+) -> Result<char, Error>
+where
+    F: FnOnce() -> String,
+{
+    match common.first(table) {
+        Some(item) if common.len() == 1 || !strict => Ok(item),
+        Some(_) => Err(Error::SeveralCommon(
+            first,
+            last,
+            common.to_string(table),
+            name(),
+        )),
+        None => Err(Error::NoCommon(first, last, name())),
+    }
+}
+
+#[cfg(test)]
+pub(crate) const TEST_STR: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
@@ -127,30 +456,99 @@ ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw
 "#;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
     #[test]
     fn test_parse() {
         let actual = read_input(TEST_STR.as_bytes()).unwrap();
         let expected = vec![
-            Rucksack::new(b"vJrwpWtwJgWr".to_vec(), b"hcsFMMfFFhFp".to_vec()),
-            Rucksack::new(b"jqHRNqRjqzjGDLGL".to_vec(), b"rsFMfFZSrLrFZsSL".to_vec()),
-            Rucksack::new(b"PmmdzqPrV".to_vec(), b"vPwwTWBwg".to_vec()),
-            Rucksack::new(b"wMqvLMZHhHMvwLH".to_vec(), b"jbvcjnnSBnvTQFn".to_vec()),
-            Rucksack::new(b"ttgJtRGJ".to_vec(), b"QctTZtZT".to_vec()),
-            Rucksack::new(b"CrZsJsPPZsGz".to_vec(), b"wwsLwLmpwMDw".to_vec()),
+            Rucksack::new(1, "vJrwpWtwJgWr".to_string(), "hcsFMMfFFhFp".to_string()),
+            Rucksack::new(
+                2,
+                "jqHRNqRjqzjGDLGL".to_string(),
+                "rsFMfFZSrLrFZsSL".to_string(),
+            ),
+            Rucksack::new(3, "PmmdzqPrV".to_string(), "vPwwTWBwg".to_string()),
+            Rucksack::new(
+                4,
+                "wMqvLMZHhHMvwLH".to_string(),
+                "jbvcjnnSBnvTQFn".to_string(),
+            ),
+            Rucksack::new(5, "ttgJtRGJ".to_string(), "QctTZtZT".to_string()),
+            Rucksack::new(6, "CrZsJsPPZsGz".to_string(), "wwsLwLmpwMDw".to_string()),
         ];
         assert_eq!(actual, expected);
     }
 
     #[test]
-    fn test_get_priority() {
-        let actual = b"azAZ"
+    fn test_parse_rucksacks() {
+        let table = PriorityTable::default();
+        let actual = parse_rucksacks(TEST_STR, &table)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(actual[0].left(), "vJrwpWtwJgWr");
+        assert_eq!(actual[0].right(), "hcsFMMfFFhFp");
+
+        let owned = read_input(TEST_STR.as_bytes()).unwrap();
+        let expected = actual
             .iter()
             .copied()
-            .map(get_priority)
+            .map(Rucksack::from)
+            .collect::<Vec<_>>();
+        assert_eq!(owned, expected);
+
+        assert_eq!(compute_priorities(actual.clone()).unwrap(), 157);
+        assert_eq!(compute_chunked_priorities(actual).unwrap(), 70);
+
+        let actual = parse_rucksacks("abab\n\nabc\n", &table).collect::<Result<Vec<_>, _>>();
+        assert!(matches!(actual, Err(Error::OddLength(3, 3))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let table = PriorityTable::default();
+        assert!(matches!(
+            read_line(3, "abc", &table),
+            Err(Error::OddLength(3, 3))
+        ));
+        assert!(matches!(
+            read_line(4, "ab-d", &table),
+            Err(Error::InvalidItem(4, 3, '-'))
+        ));
+
+        let actual = read_input("abab\n\nabc\n".as_bytes());
+        assert!(matches!(actual, Err(Error::OddLength(3, 3))));
+    }
+
+    #[test]
+    fn test_read_input_lenient() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabc\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\nab1b\n";
+        let actual = read_input_lenient(input.as_bytes()).unwrap();
+        assert_eq!(actual.rucksacks.len(), 2);
+        assert!(matches!(
+            actual.issues.as_slice(),
+            [Error::OddLength(2, 3), Error::InvalidItem(5, 3, '1')]
+        ));
+        assert_eq!(compute_priorities(actual.rucksacks).unwrap(), 16 + 38);
+    }
+
+    #[test]
+    fn test_get_priority() {
+        let table = PriorityTable::default();
+        let actual = "azAZ"
+            .chars()
+            .map(|c| get_priority(c, &table))
             .collect::<Result<Vec<_>, _>>()
             .unwrap();
         let expected = vec![1, 26, 27, 52];
         assert_eq!(actual, expected);
+        assert!(matches!(
+            get_priority('1', &table),
+            Err(Error::Invalid('1'))
+        ));
     }
 
     #[test]
@@ -160,14 +558,122 @@ CrZsJsPPZsGzwwsLwLmpwMDw
         assert_eq!(actual, 157);
     }
 
+    #[test]
+    fn test_compute_chunked_priorities() {
+        let rucksacks = read_input(TEST_STR.as_bytes()).unwrap();
+        let actual = compute_chunked_priorities(rucksacks).unwrap();
+        assert_eq!(actual, 70);
+    }
+
+    #[test]
+    fn test_custom_table() {
+        let table = PriorityTable::from_str("a-z=1 A-Z=27 0-9=53 é=100").unwrap();
+        let input = "ab1éc1
+xyéézw
+qr9é9p
+";
+        assert!(matches!(
+            read_input(input.as_bytes()),
+            Err(Error::InvalidItem(1, 3, '1'))
+        ));
+
+        let rucksacks = read_input_with(input.as_bytes(), &table).unwrap();
+        assert_eq!(rucksacks[1].to_string(), "xyéézw");
+        let actual = compute_priorities_with(rucksacks.clone(), &table).unwrap();
+        assert_eq!(actual, 54 + 100 + 62);
+        let actual = compute_chunked_priorities_with(rucksacks, &table).unwrap();
+        assert_eq!(actual, 100);
+    }
+
+    #[test]
+    fn test_common_items() {
+        let table = PriorityTable::default();
+        let rucksack = read_line(1, "abcBAcaC", &table).unwrap();
+        let actual = rucksack.common_items(&table).unwrap();
+        assert_eq!(actual.iter(&table).collect::<Vec<_>>(), vec!['a', 'c']);
+
+        let rucksacks = vec![rucksack];
+        assert_eq!(compute_priorities(rucksacks.clone()).unwrap(), 1);
+        let actual = compute_priorities_strict(rucksacks);
+        assert!(matches!(
+            actual,
+            Err(Error::SeveralCommon(1, 1, items, line)) if items == "ac" && line == "abcBAcaC"
+        ));
+
+        let rucksacks = vec![read_line(4, "abcd", &table).unwrap()];
+        let actual = compute_priorities_strict(rucksacks);
+        assert!(matches!(actual, Err(Error::NoCommon(4, 4, line)) if line == "abcd"));
+
+        let rucksacks = read_input("abab\n\ncdcd\n".as_bytes()).unwrap();
+        let grouping = Grouping {
+            size: 2,
+            incomplete: IncompletePolicy::Fail,
+            strict: true,
+        };
+        let actual = compute_grouped_priorities(rucksacks, &grouping).unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "No common item found in abab, cdcd on lines 1-3"
+        );
+    }
+
+    #[test]
+    fn test_compute_strict() {
+        let rucksacks = read_input(TEST_STR.as_bytes()).unwrap();
+        let actual = compute_priorities_strict(rucksacks.clone()).unwrap();
+        assert_eq!(actual, 157);
+        let actual = compute_chunked_priorities_strict(rucksacks).unwrap();
+        assert_eq!(actual, 70);
+    }
+
+    #[test]
+    fn test_compute_grouped_priorities() {
+        let rucksacks = read_input(TEST_STR.as_bytes()).unwrap();
+        let grouping = Grouping {
+            size: 2,
+            ..Grouping::default()
+        };
+        let actual = compute_grouped_priorities(rucksacks.clone(), &grouping).unwrap();
+        assert_eq!(actual, 56);
+
+        let grouping = Grouping {
+            size: 4,
+            ..Grouping::default()
+        };
+        let actual = compute_grouped_priorities(rucksacks.clone(), &grouping);
+        assert!(matches!(actual, Err(Error::IncompleteGroup(5, 6))));
+
+        let grouping = Grouping {
+            size: 2,
+            incomplete: IncompletePolicy::Skip,
+            strict: false,
+        };
+        let actual = compute_grouped_priorities(rucksacks[..5].to_vec(), &grouping).unwrap();
+        assert_eq!(actual, 6 + 17);
+
+        let grouping = Grouping {
+            size: 0,
+            ..Grouping::default()
+        };
+        let actual = compute_grouped_priorities(rucksacks, &grouping);
+        assert!(matches!(actual, Err(Error::GroupSize(0))));
+
+        // Blank lines are skipped, so rucksacks are not numbered like lines
+        let input = "abab\n\n\ncdcd\n\nefef\nghgh\n";
+        let rucksacks = read_input(input.as_bytes()).unwrap();
+        let actual = compute_grouped_priorities(rucksacks, &Grouping::default());
+        assert!(matches!(actual, Err(Error::IncompleteGroup(7, 7))));
+    }
+
     #[test]
     fn test_find_common() {
-        let list1 = vec![1, 2, 3];
-        let list2 = vec![2, 3, 4];
-        let list3 = vec![2, 4, 5, 6];
+        let table = PriorityTable::default();
+        let list1 = ItemSet::from_items("abc", &table).unwrap();
+        let list2 = ItemSet::from_items("bcd", &table).unwrap();
+        let list3 = ItemSet::from_items("bdef", &table).unwrap();
 
-        let chunk = vec![list1.into_iter(), list2.into_iter(), list3.into_iter()];
-        let actual = find_common(chunk.into_iter());
-        assert_eq!(actual, Some(2));
+        let chunk = vec![list1, list2, list3];
+        let actual = find_common(chunk.into_iter()).and_then(|common| common.first(&table));
+        assert_eq!(actual, Some('b'));
     }
 }
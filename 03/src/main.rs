@@ -1,5 +1,8 @@
-use advent2022_03::{compute_chunked_priorities, compute_priorities, read_input};
+use advent2022_03::{
+    analyze, compute_chunked_priorities, compute_priorities, read_input, Grouping, PriorityTable,
+};
 use anyhow::Result;
+use std::env;
 use std::fs::File;
 use std::path::Path;
 
@@ -7,6 +10,16 @@ fn main() -> Result<()> {
     let path = Path::new("resources/03/input");
     let file = File::open(path)?;
     let rucksacks = read_input(file)?;
+
+    // Print the item frequencies instead of the priorities
+    if env::args().skip(1).any(|arg| arg == "--report") {
+        print!(
+            "{}",
+            analyze(&rucksacks, &PriorityTable::default(), &Grouping::default())?
+        );
+        return Ok(());
+    }
+
     let priorities = compute_priorities(rucksacks.clone())?;
     println!("{priorities}");
 
@@ -0,0 +1,159 @@
+use crate::Error;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// Item sets are stored in a u64 bitmask
+const MAX_ITEMS: usize = 64;
+
+/// Priorities of every item type.
+///
+/// A spec is a list of `item=priority` or `first-last=priority` entries, separated
+/// by whitespace or commas. Items in a range get consecutive priorities.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriorityTable {
+    // Sorted by priority
+    items: Vec<(char, i32)>,
+    // Slots of ASCII items, looked up directly as they are the common case
+    ascii: [Option<u8>; 128],
+    others: HashMap<char, usize>,
+}
+
+impl Default for PriorityTable {
+    fn default() -> Self {
+        let items = ('a'..='z').zip(1..).chain(('A'..='Z').zip(27..));
+        PriorityTable::new(items).expect("The default priority table should be valid")
+    }
+}
+
+impl PriorityTable {
+    pub fn new<I>(items: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (char, i32)>,
+    {
+        let mut items = items.into_iter().collect::<Vec<_>>();
+        if items.len() > MAX_ITEMS {
+            return Err(Error::TooManyItems(items.len()));
+        }
+        items.sort_by_key(|(item, priority)| (*priority, *item));
+
+        let mut ascii = [None; 128];
+        let mut others = HashMap::new();
+        for (slot, (item, _)) in items.iter().enumerate() {
+            let duplicate = match ascii.get_mut(*item as usize) {
+                Some(ascii) => ascii.replace(slot as u8).is_some(),
+                None => others.insert(*item, slot).is_some(),
+            };
+            if duplicate {
+                return Err(Error::PriorityTable(item.to_string()));
+            }
+        }
+        Ok(PriorityTable {
+            items,
+            ascii,
+            others,
+        })
+    }
+
+    pub fn priority(&self, item: char) -> Option<i32> {
+        self.slot(item).map(|slot| self.items[slot].1)
+    }
+
+    /// Every item type, sorted by priority
+    pub fn items(&self) -> impl Iterator<Item = char> + '_ {
+        self.items.iter().map(|(item, _)| *item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub(crate) fn slot(&self, item: char) -> Option<usize> {
+        match self.ascii.get(item as usize) {
+            Some(slot) => slot.map(usize::from),
+            None => self.others.get(&item).copied(),
+        }
+    }
+
+    pub(crate) fn item(&self, slot: usize) -> Option<char> {
+        self.items.get(slot).map(|(item, _)| *item)
+    }
+}
+
+impl FromStr for PriorityTable {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|entry| !entry.is_empty());
+
+        let mut items = Vec::new();
+        for entry in entries {
+            let (range, priority) = entry
+                .rsplit_once('=')
+                .and_then(|(range, priority)| Some((range, i32::from_str(priority).ok()?)))
+                .ok_or_else(|| Error::PriorityTable(entry.to_string()))?;
+
+            let range = range.chars().collect::<Vec<_>>();
+            match range.as_slice() {
+                [item] => items.push((*item, priority)),
+                [first, '-', last] if first <= last => {
+                    for (offset, item) in (*first..=*last).enumerate() {
+                        let priority = i32::try_from(offset)
+                            .ok()
+                            .and_then(|offset| priority.checked_add(offset))
+                            .ok_or_else(|| Error::PriorityTable(entry.to_string()))?;
+                        items.push((item, priority));
+                    }
+                }
+                _ => return Err(Error::PriorityTable(entry.to_string())),
+            }
+        }
+        PriorityTable::new(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec() {
+        let actual = PriorityTable::from_str("a-z=1, A-Z=27").unwrap();
+        assert_eq!(actual, PriorityTable::default());
+
+        let actual = PriorityTable::from_str("0-9=53 é=63 -=64").unwrap();
+        assert_eq!(actual.priority('0'), Some(53));
+        assert_eq!(actual.priority('9'), Some(62));
+        assert_eq!(actual.priority('é'), Some(63));
+        assert_eq!(actual.priority('-'), Some(64));
+        assert_eq!(actual.priority('a'), None);
+
+        assert!(matches!(
+            PriorityTable::from_str("a-c=1 b=5"),
+            Err(Error::PriorityTable(item)) if item == "b"
+        ));
+        assert!(matches!(
+            PriorityTable::from_str("é=1 é=2"),
+            Err(Error::PriorityTable(item)) if item == "é"
+        ));
+        let actual = PriorityTable::from_str("a-b=2147483646").unwrap();
+        assert_eq!(actual.priority('b'), Some(i32::MAX));
+        assert!(matches!(
+            PriorityTable::from_str("a-c=2147483646"),
+            Err(Error::PriorityTable(entry)) if entry == "a-c=2147483646"
+        ));
+        assert!(matches!(
+            PriorityTable::from_str("a-z=x"),
+            Err(Error::PriorityTable(_))
+        ));
+        assert!(matches!(
+            PriorityTable::from_str("a-z=1 A-Z=27 0-9=53 _=63 #=64 ~=65"),
+            Err(Error::TooManyItems(65))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Compartments, Rucksack};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Compartment {
+    Left,
+    Right,
+}
+
+/// `count` items of type `item` moved out of the compartment `from`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub item: char,
+    pub count: usize,
+    pub from: Compartment,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reorganization {
+    pub moves: Vec<Move>,
+    pub rucksack: Rucksack,
+}
+
+impl Reorganization {
+    /// Number of items moved
+    pub fn len(&self) -> usize {
+        self.moves.iter().map(|m| m.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+/// Moves the fewest items so that no item type is in both compartments, keeping both compartments
+/// the same size.
+///
+/// Returns `None` if the item types cannot be split in two halves.
+pub fn reorganize<R>(rucksack: &R) -> Option<Reorganization>
+where
+    R: Compartments,
+{
+    let mut counts = BTreeMap::<char, (usize, usize)>::new();
+    for item in rucksack.left().chars() {
+        counts.entry(item).or_default().0 += 1;
+    }
+    for item in rucksack.right().chars() {
+        counts.entry(item).or_default().1 += 1;
+    }
+    let counts = counts.into_iter().collect::<Vec<_>>();
+    let size = rucksack.left().chars().count();
+
+    // best[i][s]: fewest moves to place the first i types with s items on the left,
+    // and the compartment chosen for the i-th type
+    let mut best = vec![vec![None; size + 1]; counts.len() + 1];
+    best[0][0] = Some((0, Compartment::Left));
+    for (i, (_, (left, right))) in counts.iter().enumerate() {
+        for s in 0..=size {
+            if let Some((moves, _)) = best[i][s] {
+                let options = [
+                    (s + left + right, moves + right, Compartment::Left),
+                    (s, moves + left, Compartment::Right),
+                ];
+                for (s, moves, compartment) in options {
+                    let next = best[i + 1].get_mut(s);
+                    if let Some(next) = next.filter(|n| n.is_none_or(|(m, _)| moves < m)) {
+                        *next = Some((moves, compartment));
+                    }
+                }
+            }
+        }
+    }
+    best[counts.len()][size]?;
+
+    let mut sides = BTreeMap::new();
+    let mut s = size;
+    for i in (0..counts.len()).rev() {
+        let (item, (left, right)) = counts[i];
+        let (_, compartment) = best[i + 1][s]?;
+        if compartment == Compartment::Left {
+            s -= left + right;
+        }
+        sides.insert(item, compartment);
+    }
+
+    let moves = counts
+        .iter()
+        .filter_map(|(item, (left, right))| match sides[item] {
+            Compartment::Left if *right > 0 => Some(Move {
+                item: *item,
+                count: *right,
+                from: Compartment::Right,
+            }),
+            Compartment::Right if *left > 0 => Some(Move {
+                item: *item,
+                count: *left,
+                from: Compartment::Left,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    // Items which are not moved keep their order
+    let items = rucksack.left().chars().chain(rucksack.right().chars());
+    let (left, right) = items.partition(|item| sides[item] == Compartment::Left);
+    Some(Reorganization {
+        moves,
+        rucksack: Rucksack::new(rucksack.line(), left, right),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_line, PriorityTable};
+
+    #[test]
+    fn test_reorganize() {
+        let table = PriorityTable::default();
+        let rucksack = read_line(1, "vJrwpWtwJgWrhcsFMMfFFhFp", &table).unwrap();
+        let actual = reorganize(&rucksack).unwrap();
+        assert_eq!(actual.len(), 2);
+        assert!(actual.rucksack.common_items(&table).unwrap().is_empty());
+        assert_eq!(actual.rucksack.left.len(), actual.rucksack.right.len());
+        assert_eq!(
+            actual.rucksack.items(&table).unwrap(),
+            rucksack.items(&table).unwrap()
+        );
+
+        let rucksack = read_line(1, "abcd", &table).unwrap();
+        let actual = reorganize(&rucksack).unwrap();
+        assert!(actual.is_empty());
+        assert_eq!(actual.rucksack, rucksack);
+
+        let rucksack = read_line(1, "aaab", &table).unwrap();
+        assert_eq!(reorganize(&rucksack), None);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    Adjacent, Disjoint, Equal, Error, FullyContains, Overlaps, PairMatchingPredicate, SectionId,
+    StrictlyContains, Touching,
+};
+use std::iter::Peekable;
+use std::ops::RangeInclusive;
+use std::str::{CharIndices, FromStr};
+
+/// A predicate chosen at runtime.
+///
+/// Expressions combine predicate names with `&`, `|`, `!` and parentheses, for
+/// example `overlaps & !contains`. `!` binds tighter than `&`, which binds tighter than `|`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Predicate {
+    FullyContains,
+    StrictlyContains,
+    Equal,
+    Overlaps,
+    Disjoint,
+    Adjacent,
+    Touching,
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    pub fn check<T>(&self, left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool
+    where
+        T: SectionId,
+    {
+        match self {
+            Predicate::FullyContains => FullyContains::check(left, right),
+            Predicate::StrictlyContains => StrictlyContains::check(left, right),
+            Predicate::Equal => Equal::check(left, right),
+            Predicate::Overlaps => Overlaps::check(left, right),
+            Predicate::Disjoint => Disjoint::check(left, right),
+            Predicate::Adjacent => Adjacent::check(left, right),
+            Predicate::Touching => Touching::check(left, right),
+            Predicate::And(a, b) => a.check(left, right) && b.check(left, right),
+            Predicate::Or(a, b) => a.check(left, right) || b.check(left, right),
+            Predicate::Not(a) => !a.check(left, right),
+        }
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            input: s,
+            chars: s.char_indices().peekable(),
+        };
+        let predicate = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(predicate),
+            Some(_) => Err(Error::Predicate(s.to_string())),
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn parse_or(&mut self) -> Result<Predicate, Error> {
+        let mut predicate = self.parse_and()?;
+        while self.eat('|') {
+            predicate = Predicate::Or(Box::new(predicate), Box::new(self.parse_and()?));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_and(&mut self) -> Result<Predicate, Error> {
+        let mut predicate = self.parse_not()?;
+        while self.eat('&') {
+            predicate = Predicate::And(Box::new(predicate), Box::new(self.parse_not()?));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_not(&mut self) -> Result<Predicate, Error> {
+        if self.eat('!') {
+            Ok(Predicate::Not(Box::new(self.parse_not()?)))
+        } else if self.eat('(') {
+            let predicate = self.parse_or()?;
+            if self.eat(')') {
+                Ok(predicate)
+            } else {
+                Err(self.error())
+            }
+        } else {
+            self.parse_name()
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<Predicate, Error> {
+        let start = match self.peek() {
+            Some((start, _)) => start,
+            None => return Err(self.error()),
+        };
+        let mut end = start;
+        while let Some((i, c)) = self
+            .chars
+            .next_if(|(_, c)| c.is_alphanumeric() || *c == '-')
+        {
+            end = i + c.len_utf8();
+        }
+
+        match &self.input[start..end] {
+            "contains" => Ok(Predicate::FullyContains),
+            "strictly-contains" => Ok(Predicate::StrictlyContains),
+            "equal" => Ok(Predicate::Equal),
+            "overlaps" => Ok(Predicate::Overlaps),
+            "disjoint" => Ok(Predicate::Disjoint),
+            "adjacent" => Ok(Predicate::Adjacent),
+            "touching" => Ok(Predicate::Touching),
+            _ => Err(self.error()),
+        }
+    }
+
+    // Skips whitespace, and returns the next character
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        match self.peek() {
+            Some((_, c)) if c == expected => {
+                self.chars.next();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn error(&self) -> Error {
+        Error::Predicate(self.input.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let actual = Predicate::from_str("overlaps & !contains").unwrap();
+        let expected = Predicate::And(
+            Box::new(Predicate::Overlaps),
+            Box::new(Predicate::Not(Box::new(Predicate::FullyContains))),
+        );
+        assert_eq!(actual, expected);
+
+        let actual = Predicate::from_str("equal|adjacent&!(disjoint)").unwrap();
+        let expected = Predicate::Or(
+            Box::new(Predicate::Equal),
+            Box::new(Predicate::And(
+                Box::new(Predicate::Adjacent),
+                Box::new(Predicate::Not(Box::new(Predicate::Disjoint))),
+            )),
+        );
+        assert_eq!(actual, expected);
+
+        for input in ["", "overlaps &", "(touching", "contain", "equal equal"] {
+            assert!(matches!(
+                Predicate::from_str(input),
+                Err(Error::Predicate(s)) if s == input
+            ));
+        }
+    }
+}
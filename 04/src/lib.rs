@@ -3,62 +3,85 @@ use std::ops::RangeInclusive;
 use std::str::FromStr;
 use thiserror::Error;
 
+mod expression;
+mod predicates;
+
+pub use expression::Predicate;
+pub use predicates::{
+    Adjacent, And, Disjoint, Equal, FullyContains, Not, Or, Overlaps, PairMatchingPredicate,
+    SectionId, StrictlyContains, Touching,
+};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("I/O error")]
     IO(#[from] IoError),
-    #[error("Invalid input {}", .0)]
-    Parse(String),
+    #[error("Expected 2 ranges, found {} on line {}", .1, .0)]
+    RangeCount(usize, usize),
+    #[error("Missing dash in range at column {} on line {}", .1, .0)]
+    MissingDash(usize, usize),
+    #[error("Extra field in range at column {} on line {}", .1, .0)]
+    ExtraField(usize, usize),
+    #[error("Invalid number {} at column {} on line {}", .2, .1, .0)]
+    InvalidNumber(usize, usize, String),
+    #[error("Reversed range {} at column {} on line {}", .2, .1, .0)]
+    Reversed(usize, usize, String),
+    #[error("Invalid predicate {}", .0)]
+    Predicate(String),
+}
+
+/// What to do with ranges whose start is after their end
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReversedPolicy {
+    #[default]
+    Fail,
+    Swap,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Pair {
-    left: RangeInclusive<i32>,
-    right: RangeInclusive<i32>,
+pub struct Pair<T = i32> {
+    left: RangeInclusive<T>,
+    right: RangeInclusive<T>,
 }
 
-impl Pair {
-    fn new(left: RangeInclusive<i32>, right: RangeInclusive<i32>) -> Self {
+impl<T> Pair<T> {
+    fn new(left: RangeInclusive<T>, right: RangeInclusive<T>) -> Self {
         Pair { left, right }
     }
 
     fn check_match<P>(&self) -> bool
     where
-        P: PairMatchingPredicate<i32>,
+        P: PairMatchingPredicate<T>,
     {
-        P::check(&self.left, &self.right) || P::check(&self.right, &self.left)
+        P::check(&self.left, &self.right)
     }
-}
 
-pub trait PairMatchingPredicate<T> {
-    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool;
+    fn check_match_with(&self, predicate: &Predicate) -> bool
+    where
+        T: SectionId,
+    {
+        predicate.check(&self.left, &self.right)
+    }
 }
 
-pub struct FullyContains;
-
-impl<T> PairMatchingPredicate<T> for FullyContains
+pub fn read_input<R>(read: R) -> Result<Vec<Pair>, Error>
 where
-    T: PartialOrd,
+    R: Read,
 {
-    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
-        left.start() >= right.start() && left.end() <= right.end()
-    }
+    read_input_with(read, ReversedPolicy::default())
 }
 
-pub struct Overlaps;
-
-impl<T> PairMatchingPredicate<T> for Overlaps
+pub fn read_input_with<R>(read: R, reversed: ReversedPolicy) -> Result<Vec<Pair>, Error>
 where
-    T: PartialOrd,
+    R: Read,
 {
-    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
-        left.start() <= right.start() && left.end() >= right.start()
-            || right.start() <= left.start() && right.end() >= left.end()
-    }
+    read_input_as(read, reversed)
 }
 
-pub fn read_input<R>(read: R) -> Result<Vec<Pair>, Error>
+/// Like `read_input_with`, with section IDs of any integer type, including negative ones
+pub fn read_input_as<T, R>(read: R, reversed: ReversedPolicy) -> Result<Vec<Pair<T>>, Error>
 where
+    T: SectionId + FromStr,
     R: Read,
 {
     let lines = BufReader::new(read).lines();
@@ -67,9 +90,9 @@ where
         .collect::<Result<Vec<_>, _>>()?;
     let pairs = lines
         .iter()
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(|l| read_line(l).ok_or_else(|| Error::Parse(l.to_string())))
+        .enumerate()
+        .filter(|(_, s)| !s.trim().is_empty())
+        .map(|(i, s)| read_line(i + 1, s.trim_end(), reversed))
         .collect::<Result<Vec<_>, _>>()?;
     Ok(pairs)
 }
@@ -77,32 +100,94 @@ where
 pub fn count_matching<P>(pairs: &[Pair]) -> usize
 where
     P: PairMatchingPredicate<i32>,
+{
+    count_matching_as::<P, _>(pairs)
+}
+
+/// Like `count_matching`, with section IDs of any integer type
+pub fn count_matching_as<P, T>(pairs: &[Pair<T>]) -> usize
+where
+    P: PairMatchingPredicate<T>,
 {
     pairs.iter().filter(|pair| pair.check_match::<P>()).count()
 }
 
-fn read_line(input: &str) -> Option<Pair> {
-    let mut iter = input.split(',');
-    if let (Some(left), Some(right), None) = (iter.next(), iter.next(), iter.next()) {
-        let left = parse_range(left)?;
-        let right = parse_range(right)?;
-        Some(Pair::new(left, right))
+/// Like `count_matching`, with a predicate chosen at runtime
+pub fn count_matching_with<T>(pairs: &[Pair<T>], predicate: &Predicate) -> usize
+where
+    T: SectionId,
+{
+    pairs
+        .iter()
+        .filter(|pair| pair.check_match_with(predicate))
+        .count()
+}
+
+// Lines and columns are numbered from 1
+fn read_line<T>(line: usize, input: &str, reversed: ReversedPolicy) -> Result<Pair<T>, Error>
+where
+    T: SectionId + FromStr,
+{
+    let trimmed = input.trim_start();
+    let ranges = trimmed.split(',').collect::<Vec<_>>();
+    if let [left, right] = ranges.as_slice() {
+        let column = input[..input.len() - trimmed.len()].chars().count() + 1;
+        let right_column = column + left.chars().count() + 1;
+        let left = parse_range(line, column, left, reversed)?;
+        let right = parse_range(line, right_column, right, reversed)?;
+        Ok(Pair::new(left, right))
     } else {
-        None
+        Err(Error::RangeCount(line, ranges.len()))
     }
 }
 
-fn parse_range(input: &str) -> Option<RangeInclusive<i32>> {
-    let mut iter = input.split('-');
-    if let (Some(left), Some(right), None) = (iter.next(), iter.next(), iter.next()) {
-        let left = i32::from_str(left).ok()?;
-        let right = i32::from_str(right).ok()?;
-        Some(left..=right)
-    } else {
-        None
+fn parse_range<T>(
+    line: usize,
+    column: usize,
+    input: &str,
+    reversed: ReversedPolicy,
+) -> Result<RangeInclusive<T>, Error>
+where
+    T: SectionId + FromStr,
+{
+    // Bounds might be negative, so the separator is the first dash after the first character
+    let dash = find_dash(input).ok_or(Error::MissingDash(line, column))?;
+    let (left, right) = (&input[..dash], &input[dash + 1..]);
+    let right_column = column + left.chars().count() + 1;
+    if let Some(extra) = find_dash(right) {
+        return Err(Error::ExtraField(
+            line,
+            right_column + right[..extra].chars().count(),
+        ));
+    }
+
+    let start = parse_bound(line, column, left)?;
+    let end = parse_bound(line, right_column, right)?;
+    match reversed {
+        ReversedPolicy::Fail if start > end => {
+            Err(Error::Reversed(line, column, input.to_string()))
+        }
+        ReversedPolicy::Swap if start > end => Ok(end..=start),
+        _ => Ok(start..=end),
     }
 }
 
+// Byte index of the first dash after the first character, which may be multi-byte
+fn find_dash(input: &str) -> Option<usize> {
+    input
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '-')
+        .map(|(i, _)| i)
+}
+
+fn parse_bound<T>(line: usize, column: usize, input: &str) -> Result<T, Error>
+where
+    T: FromStr,
+{
+    T::from_str(input).map_err(|_| Error::InvalidNumber(line, column, input.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +214,55 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_parse_types() {
+        let actual = read_input_as::<u64, _>(
+            "4000000000-5000000000,1-2\n".as_bytes(),
+            ReversedPolicy::Fail,
+        )
+        .unwrap();
+        assert_eq!(
+            actual,
+            vec![Pair::new(4_000_000_000..=5_000_000_000, 1..=2)]
+        );
+
+        let actual =
+            read_input_as::<i8, _>("-5--3,-4-0\n".as_bytes(), ReversedPolicy::Fail).unwrap();
+        assert_eq!(actual, vec![Pair::new(-5..=-3, -4..=0)]);
+        assert_eq!(count_matching_as::<Overlaps, _>(&actual), 1);
+
+        let actual = read_input_as::<u8, _>("1-300,1-2\n".as_bytes(), ReversedPolicy::Fail);
+        assert!(matches!(actual, Err(Error::InvalidNumber(1, 3, n)) if n == "300"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let read = |input: &str| read_input(input.as_bytes());
+        assert!(matches!(read("1-2\n"), Err(Error::RangeCount(1, 1))));
+        assert!(matches!(
+            read("\n1-2,3-4,5-6\n"),
+            Err(Error::RangeCount(2, 3))
+        ));
+        assert!(matches!(read("1-2,34\n"), Err(Error::MissingDash(1, 5))));
+        assert!(matches!(
+            read("  1-2-3,1-2\n"),
+            Err(Error::ExtraField(1, 6))
+        ));
+        assert!(matches!(read("1-2,3-x\n"), Err(Error::InvalidNumber(1, 7, n)) if n == "x"));
+        assert!(matches!(read("1-2,8-2\n"), Err(Error::Reversed(1, 5, r)) if r == "8-2"));
+
+        // Columns count characters, not bytes
+        assert!(matches!(read("é-3,1-2\n"), Err(Error::InvalidNumber(1, 1, n)) if n == "é"));
+        assert!(matches!(read("1-2,é-x\n"), Err(Error::InvalidNumber(1, 5, n)) if n == "é"));
+        assert!(matches!(read("é-2-3,1-2\n"), Err(Error::ExtraField(1, 4))));
+        assert!(
+            matches!(read("\u{3000}1-2,3-x\n"), Err(Error::InvalidNumber(1, 8, n)) if n == "x")
+        );
+
+        let actual = read_input_with("8-2,-1--3\n".as_bytes(), ReversedPolicy::Swap);
+        assert_eq!(actual.unwrap(), vec![Pair::new(2..=8, -3..=-1)]);
+    }
+
     #[test]
     fn test_algo() {
         let pairs = read_input(TEST_STR.as_bytes()).unwrap();
@@ -138,4 +272,26 @@ mod tests {
         let overlaps = count_matching::<Overlaps>(&pairs);
         assert_eq!(overlaps, 4);
     }
+
+    #[test]
+    fn test_combinators() {
+        let pairs = read_input(TEST_STR.as_bytes()).unwrap();
+        assert_eq!(
+            count_matching::<And<Overlaps, Not<FullyContains>>>(&pairs),
+            2
+        );
+        assert_eq!(count_matching::<Or<Disjoint, Equal>>(&pairs), 2);
+        assert_eq!(count_matching::<StrictlyContains>(&pairs), 2);
+        assert_eq!(count_matching::<Adjacent>(&pairs), 1);
+        assert_eq!(count_matching::<Touching>(&pairs), 5);
+    }
+
+    #[test]
+    fn test_runtime_predicates() {
+        let pairs = read_input(TEST_STR.as_bytes()).unwrap();
+        let predicate = Predicate::from_str("overlaps & !contains").unwrap();
+        assert_eq!(count_matching_with(&pairs, &predicate), 2);
+        let predicate = Predicate::from_str("touching | equal").unwrap();
+        assert_eq!(count_matching_with(&pairs, &predicate), 5);
+    }
 }
@@ -1,12 +1,27 @@
-use advent2022_04::{count_matching, read_input, FullyContains, Overlaps};
+use advent2022_04::{
+    count_matching, count_matching_with, read_input, FullyContains, Overlaps, Predicate,
+};
+use advent2022_utils::find_option;
 use anyhow::Result;
+use std::env;
 use std::fs::File;
 use std::path::Path;
+use std::str::FromStr;
 
 fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
     let path = Path::new("resources/04/input");
     let file = File::open(path)?;
     let pairs = read_input(file)?;
+
+    // Count the pairs matching a custom predicate instead
+    if let Some(predicate) = find_option(&args, "--predicate")? {
+        let predicate = Predicate::from_str(predicate)?;
+        let count = count_matching_with(&pairs, &predicate);
+        println!("{count}");
+        return Ok(());
+    }
+
     let fully_contains = count_matching::<FullyContains>(&pairs);
     println!("{fully_contains}");
 
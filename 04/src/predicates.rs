@@ -0,0 +1,155 @@
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+/// A property of a pair of ranges, which does not depend on the order of the ranges
+pub trait PairMatchingPredicate<T> {
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool;
+}
+
+/// Section IDs which have a next section, to find adjacent ranges
+pub trait SectionId: PartialOrd + Sized {
+    fn next(&self) -> Option<Self>;
+}
+
+macro_rules! impl_section_id {
+    ($($t:ty),*) => {
+        $(
+            impl SectionId for $t {
+                fn next(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_section_id!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// One range is contained in the other
+pub struct FullyContains;
+
+impl<T> PairMatchingPredicate<T> for FullyContains
+where
+    T: PartialOrd,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        contains(left, right) || contains(right, left)
+    }
+}
+
+/// One range is contained in the other, which is larger
+pub struct StrictlyContains;
+
+impl<T> PairMatchingPredicate<T> for StrictlyContains
+where
+    T: PartialOrd,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        FullyContains::check(left, right) && !Equal::check(left, right)
+    }
+}
+
+pub struct Equal;
+
+impl<T> PairMatchingPredicate<T> for Equal
+where
+    T: PartialOrd,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        left.start() == right.start() && left.end() == right.end()
+    }
+}
+
+pub struct Overlaps;
+
+impl<T> PairMatchingPredicate<T> for Overlaps
+where
+    T: PartialOrd,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        left.start() <= right.end() && right.start() <= left.end()
+    }
+}
+
+pub type Disjoint = Not<Overlaps>;
+
+/// The ranges do not overlap, but there is no section between them
+pub struct Adjacent;
+
+impl<T> PairMatchingPredicate<T> for Adjacent
+where
+    T: SectionId,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        left.end().next().as_ref() == Some(right.start())
+            || right.end().next().as_ref() == Some(left.start())
+    }
+}
+
+pub type Touching = Or<Overlaps, Adjacent>;
+
+pub struct And<A, B>(PhantomData<(A, B)>);
+
+impl<T, A, B> PairMatchingPredicate<T> for And<A, B>
+where
+    A: PairMatchingPredicate<T>,
+    B: PairMatchingPredicate<T>,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        A::check(left, right) && B::check(left, right)
+    }
+}
+
+pub struct Or<A, B>(PhantomData<(A, B)>);
+
+impl<T, A, B> PairMatchingPredicate<T> for Or<A, B>
+where
+    A: PairMatchingPredicate<T>,
+    B: PairMatchingPredicate<T>,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        A::check(left, right) || B::check(left, right)
+    }
+}
+
+pub struct Not<A>(PhantomData<A>);
+
+impl<T, A> PairMatchingPredicate<T> for Not<A>
+where
+    A: PairMatchingPredicate<T>,
+{
+    fn check(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool {
+        !A::check(left, right)
+    }
+}
+
+fn contains<T>(outer: &RangeInclusive<T>, inner: &RangeInclusive<T>) -> bool
+where
+    T: PartialOrd,
+{
+    outer.start() <= inner.start() && inner.end() <= outer.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predicates() {
+        assert!(FullyContains::check(&(2..=8), &(3..=7)));
+        assert!(FullyContains::check(&(3..=7), &(2..=8)));
+        assert!(FullyContains::check(&(3..=7), &(3..=7)));
+        assert!(!StrictlyContains::check(&(3..=7), &(3..=7)));
+        assert!(StrictlyContains::check(&(3..=7), &(3..=8)));
+
+        assert!(Overlaps::check(&(5..=7), &(7..=9)));
+        assert!(!Overlaps::check(&(2..=4), &(6..=8)));
+        assert!(Disjoint::check(&(2..=4), &(6..=8)));
+
+        assert!(Adjacent::check(&(4..=5), &(2..=3)));
+        assert!(!Adjacent::check(&(2..=4), &(6..=8)));
+        assert!(!Adjacent::check(&(u8::MAX..=u8::MAX), &(0..=0)));
+        assert!(Touching::check(&(2..=3), &(4..=5)));
+        assert!(Touching::check(&(2..=4), &(3..=5)));
+    }
+}
@@ -49,7 +49,7 @@ where
 
         if let Some(instruction) = re.captures(&line) {
             let (count, from, to) =
-                parse_instruction_input(instruction).ok_or_else(|| Error::Parse(line))?;
+                parse_instruction_input(instruction).ok_or(Error::Parse(line))?;
             instructions.push(Instruction::new(count, from, to));
         } else {
             drawing.push_front(line);
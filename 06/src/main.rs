@@ -8,7 +8,7 @@ fn main() -> Result<()> {
     let file = File::open(path)?;
     let signals = read_input(file)?;
     assert_eq!(signals.len(), 1);
-    let signal = signals.get(0).unwrap();
+    let signal = signals.first().unwrap();
 
     let marker = find_first_marker::<4>(signal).unwrap();
     println!("{marker}");
@@ -1 +1,27 @@
+use anyhow::{Context, Result};
 
+/// Value following the `name` option in the command line arguments
+pub fn find_option<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>> {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) => {
+            let value = args
+                .get(index + 1)
+                .with_context(|| format!("Missing value for {name}"))?;
+            Ok(Some(value))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_option() {
+        let args = ["--trace", "1", "--format"].map(String::from);
+        assert_eq!(find_option(&args, "--trace").unwrap(), Some("1"));
+        assert_eq!(find_option(&args, "--report").unwrap(), None);
+        assert!(find_option(&args, "--format").is_err());
+    }
+}